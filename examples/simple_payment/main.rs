@@ -69,7 +69,7 @@ async fn main() {
         provider.get_balance(args.to, None).await.unwrap()
     );
 
-    let payment_transaction_id = zk_wallet.transfer(&payment_request, None).await.unwrap();
+    let payment_transaction_id = zk_wallet.transfer(&payment_request).await.unwrap();
     let payment_transaction_receipt = provider
         .get_transaction_receipt(payment_transaction_id)
         .await
@@ -1,4 +1,4 @@
-use ethers::abi::Contract;
+use ethers::abi::{parse_abi, Contract, Error as AbiError};
 use std::str::FromStr;
 
 const L1_DEFAULT_BRIDGE_INTERFACE: &str = include_str!("./IL1Bridge.json");
@@ -19,3 +19,63 @@ fn load_contract(raw_abi_string: &str) -> Contract {
 pub fn l1_bridge_contract() -> Contract {
     load_contract(L1_DEFAULT_BRIDGE_INTERFACE)
 }
+
+const CONTRACT_DEPLOYER_INTERFACE: &str = include_str!("./ContractDeployer.json");
+
+const L2_ETH_TOKEN_INTERFACE: &[&str] = &[
+    "function balanceOf(uint256) external view returns (uint256)",
+    "function totalSupply() external view returns (uint256)",
+    "function name() external pure returns (string)",
+    "function symbol() external pure returns (string)",
+    "function decimals() external pure returns (uint8)",
+    "function transferFromTo(address _from, address _to, uint256 _amount) external",
+    "function mint(address _account, uint256 _amount) external",
+    "function withdraw(address _l1Receiver) external payable",
+    "event Transfer(address indexed from, address indexed to, uint256 value)",
+    "event Mint(address indexed account, uint256 amount)",
+    "event Withdrawal(address indexed _l2Sender, address indexed _l1Receiver, uint256 _amount)",
+];
+
+const L1_MESSENGER_INTERFACE: &[&str] = &[
+    "function sendToL1(bytes _message) external returns (bytes32)",
+    "event L1MessageSent(address indexed _sender, bytes32 indexed _hash, bytes _message)",
+];
+
+const L2_BRIDGE_INTERFACE: &[&str] = &[
+    "function withdraw(address _l1Receiver, address _l2Token, uint256 _amount) external",
+    "function l1TokenAddress(address _l2Token) external view returns (address)",
+    "function l2TokenAddress(address _l1Token) external view returns (address)",
+    "function l1Bridge() external view returns (address)",
+    "function finalizeDeposit(address _l1Sender, address _l2Receiver, address _l1Token, uint256 _amount, bytes _data) external payable",
+];
+
+const PAYMASTER_FLOW_INTERFACE: &[&str] = &[
+    "function general(bytes input)",
+    "function approvalBased(address _token, uint256 _minAllowance, bytes _innerInput)",
+];
+
+/// Parses a human-readable interface. Its error is folded into [`AbiError`] so callers only deal
+/// with one ABI error type.
+fn parse_interface(interface: &[&str]) -> Result<Contract, AbiError> {
+    parse_abi(interface).map_err(|e| AbiError::Other(e.to_string().into()))
+}
+
+pub fn contract_deployer_contract() -> Result<Contract, AbiError> {
+    Contract::load(CONTRACT_DEPLOYER_INTERFACE.as_bytes())
+}
+
+pub fn l2_eth_token_contract() -> Result<Contract, AbiError> {
+    parse_interface(L2_ETH_TOKEN_INTERFACE)
+}
+
+pub fn l1_messenger_contract() -> Result<Contract, AbiError> {
+    parse_interface(L1_MESSENGER_INTERFACE)
+}
+
+pub fn l2_bridge_contract() -> Result<Contract, AbiError> {
+    parse_interface(L2_BRIDGE_INTERFACE)
+}
+
+pub fn paymaster_flow_contract() -> Result<Contract, AbiError> {
+    parse_interface(PAYMASTER_FLOW_INTERFACE)
+}
@@ -1,14 +1,14 @@
 use std::sync::Arc;
 
-use ethers::prelude::k256::ecdsa::{RecoveryId, Signature};
-use ethers::prelude::k256::schnorr::signature::hazmat::PrehashSigner;
+use ethers::abi::{encode, Error as AbiError, RawLog, Tokenizable};
 use ethers::prelude::signer::SignerMiddlewareError;
 use ethers::prelude::ProviderError;
 use ethers::prelude::SignerMiddleware;
 use ethers::providers::Middleware;
-use ethers::signers::Wallet;
-use ethers::types::{Address, Bytes, TransactionReceipt, U256};
-use ethers_contract::{abigen, ContractError};
+use ethers::signers::Signer;
+use ethers::types::{Address, Bytes, Log, TransactionReceipt, H256, U256};
+use ethers::utils::keccak256;
+use ethers_contract::{abigen, ContractError, EthEvent};
 
 abigen!(MainContract, "./src/abi/IZkSync.json");
 
@@ -17,15 +17,15 @@ abigen!(MainContract, "./src/abi/IZkSync.json");
 // ╚══════════════════════════════════════════════════════════════════════════════════════════╝
 
 #[derive(thiserror::Error, Debug)]
-pub enum MainContractError<M, D>
+pub enum MainContractError<M, S>
 where
     M: Middleware,
-    D: PrehashSigner<(Signature, RecoveryId)> + Sync + Send,
+    S: Signer,
 {
     #[error("Middleware error: {0}")]
-    MiddlewareError(#[from] SignerMiddlewareError<M, Wallet<D>>),
+    MiddlewareError(#[from] SignerMiddlewareError<M, S>),
     #[error("Contract error: {0}")]
-    ContractError(#[from] ContractError<SignerMiddleware<M, Wallet<D>>>),
+    ContractError(#[from] ContractError<SignerMiddleware<M, S>>),
     #[error("Provider error: {0}")]
     ProviderError(#[from] ProviderError),
     #[error("Transaction receipt not found")]
@@ -35,23 +35,23 @@ where
 // ╔══════════════════════════════════════════════════════════════════════════════════════════╗
 // ║ Decorator:                                                                               ║
 // ╚══════════════════════════════════════════════════════════════════════════════════════════╝
-type SM<M, D> = SignerMiddleware<M, Wallet<D>>;
+type SM<M, S> = SignerMiddleware<M, S>;
 
-pub struct MainContractInstance<M, D>
+pub struct MainContractInstance<M, S>
 where
     M: Middleware,
-    D: PrehashSigner<(Signature, RecoveryId)> + Sync + Send,
+    S: Signer,
 {
-    provider: Arc<SM<M, D>>,
-    contract: MainContract<SM<M, D>>,
+    provider: Arc<SM<M, S>>,
+    contract: MainContract<SM<M, S>>,
 }
 
-impl<M, D> MainContractInstance<M, D>
+impl<M, S> MainContractInstance<M, S>
 where
     M: Middleware,
-    D: PrehashSigner<(Signature, RecoveryId)> + Sync + Send,
+    S: Signer,
 {
-    pub fn new(address: Address, provider: Arc<SignerMiddleware<M, Wallet<D>>>) -> Self {
+    pub fn new(address: Address, provider: Arc<SignerMiddleware<M, S>>) -> Self {
         let contract = MainContract::new(address, Arc::clone(&provider));
         Self { provider, contract }
     }
@@ -61,14 +61,14 @@ where
         gas_price: U256,
         l2_gas_limit: U256,
         l2_gas_per_pubdata_byte_limit: U256,
-    ) -> Result<U256, ContractError<SM<M, D>>> {
+    ) -> Result<U256, ContractError<SM<M, S>>> {
         self.contract
             .l_2_transaction_base_cost(gas_price, l2_gas_limit, l2_gas_per_pubdata_byte_limit)
             .call()
             .await
     }
 
-    async fn nonce(&self) -> Result<U256, MainContractError<M, D>> {
+    async fn nonce(&self) -> Result<U256, MainContractError<M, S>> {
         let signer_address = self.provider.address();
         let nonce = self
             .provider
@@ -89,7 +89,7 @@ where
         gas_price: U256,
         gas_limit: U256,
         l1_value: U256,
-    ) -> Result<TransactionReceipt, MainContractError<M, D>> {
+    ) -> Result<TransactionReceipt, MainContractError<M, S>> {
         let nonce = self.nonce().await?;
         let function_call = self
             .contract
@@ -113,8 +113,26 @@ where
             // FIXME: Awaiting on a `PendingTransaction` results in an
             // `Option<TransactionReceipt>`. Under which circumpstances does it return `None`?
             .await?
-            .ok_or(MainContractError::<M, D>::TransactionReceiptNotFound)?;
+            .ok_or(MainContractError::<M, S>::TransactionReceiptNotFound)?;
 
         Ok(receipt)
     }
 }
+
+// ╔══════════════════════════════════════════════════════════════════════════════════════════╗
+// ║ Priority requests:                                                                       ║
+// ╚══════════════════════════════════════════════════════════════════════════════════════════╝
+
+impl L2CanonicalTransaction {
+    /// Hash of the L2 transaction, computed as the main contract does:
+    /// `keccak256(abi.encode(transaction))`.
+    pub fn hash(&self) -> H256 {
+        H256(keccak256(encode(&[self.clone().into_token()])))
+    }
+}
+
+/// Decodes the `NewPriorityRequest` event emitted by the main contract for every L1 to L2
+/// transaction, e.g. a deposit.
+pub fn decode_new_priority_request(log: &Log) -> Result<NewPriorityRequestFilter, AbiError> {
+    NewPriorityRequestFilter::decode_log(&RawLog::from(log.clone()))
+}
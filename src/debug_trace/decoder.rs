@@ -0,0 +1,351 @@
+use crate::{
+    abi,
+    zks_provider::types::{CallFrame, CallType},
+    zks_utils::{CONTRACTS_L1_MESSENGER_ADDR, CONTRACTS_L2_ETH_TOKEN_ADDR, CONTRACT_DEPLOYER_ADDR},
+};
+use ethers::{
+    abi::{decode, Abi, Function, Param, ParamType, Token},
+    types::{Address, Bytes, I256, U256},
+};
+use std::{collections::HashMap, fmt};
+
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+#[derive(Clone, Debug)]
+struct RegisteredContract {
+    name: String,
+    abi: Abi,
+}
+
+/// Decodes `callTracer` debug traces using the ABIs registered for the called addresses.
+///
+/// The zkSync system contracts (`ContractDeployer`, `L2EthToken` and `L1Messenger`) are
+/// registered by default.
+#[derive(Clone, Debug)]
+pub struct TraceDecoder {
+    contracts: HashMap<Address, RegisteredContract>,
+}
+
+impl Default for TraceDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TraceDecoder {
+    pub fn new() -> Self {
+        let system_contracts = [
+            (
+                CONTRACT_DEPLOYER_ADDR,
+                "ContractDeployer",
+                abi::contract_deployer_contract(),
+            ),
+            (
+                CONTRACTS_L2_ETH_TOKEN_ADDR,
+                "L2EthToken",
+                abi::l2_eth_token_contract(),
+            ),
+            (
+                CONTRACTS_L1_MESSENGER_ADDR,
+                "L1Messenger",
+                abi::l1_messenger_contract(),
+            ),
+        ];
+
+        system_contracts.into_iter().fold(
+            Self {
+                contracts: HashMap::new(),
+            },
+            |decoder, (address, name, abi)| match (address.parse(), abi) {
+                (Ok(address), Ok(abi)) => decoder.register_abi(address, name, abi),
+                _ => decoder,
+            },
+        )
+    }
+
+    /// Registers the ABI of the contract deployed at `address`, replacing any ABI previously
+    /// registered for it.
+    pub fn register_abi(mut self, address: Address, name: &str, abi: Abi) -> Self {
+        self.contracts.insert(
+            address,
+            RegisteredContract {
+                name: name.to_owned(),
+                abi,
+            },
+        );
+        self
+    }
+
+    pub fn decode(&self, frame: &CallFrame) -> DecodedCallFrame {
+        let contract = frame.to.and_then(|to| self.contracts.get(&to));
+        let function = match frame.call_type {
+            CallType::Create | CallType::Create2 => None,
+            _ => contract.and_then(|contract| decode_function(&contract.abi, frame)),
+        };
+        let revert = frame
+            .is_reverted()
+            .then(|| self.decode_revert(contract, frame));
+
+        DecodedCallFrame {
+            call_type: frame.call_type,
+            from: frame.from,
+            to: frame.to,
+            contract: contract.map(|contract| contract.name.clone()),
+            value: frame.value,
+            gas_used: frame.gas_used,
+            input: frame.input.clone(),
+            function,
+            revert,
+            calls: frame.calls.iter().map(|call| self.decode(call)).collect(),
+        }
+    }
+
+    /// Decodes the trace and renders it as an indented call tree.
+    pub fn render(&self, frame: &CallFrame) -> String {
+        self.decode(frame).to_string()
+    }
+
+    fn decode_revert(
+        &self,
+        contract: Option<&RegisteredContract>,
+        frame: &CallFrame,
+    ) -> DecodedRevert {
+        let selector = frame.output.get(..4).unwrap_or_default();
+        let data = frame.output.get(4..).unwrap_or_default();
+
+        let decoded = if selector == ERROR_SELECTOR {
+            decode(&[ParamType::String], data)
+                .ok()
+                .and_then(|tokens| tokens.into_iter().next()?.into_string())
+                .map(DecodedRevert::Error)
+        } else if selector == PANIC_SELECTOR {
+            decode(&[ParamType::Uint(256)], data)
+                .ok()
+                .and_then(|tokens| tokens.into_iter().next()?.into_uint())
+                .map(DecodedRevert::Panic)
+        } else {
+            // Custom errors may bubble up from callees, so every registered ABI is tried,
+            // starting with the one of the called contract.
+            contract
+                .into_iter()
+                .chain(self.contracts.values())
+                .flat_map(|contract| contract.abi.errors())
+                .find(|error| error.signature().as_bytes().get(..4) == Some(selector))
+                .and_then(|error| {
+                    let tokens = error.decode(data).ok()?;
+                    Some(DecodedRevert::Custom {
+                        name: error.name.clone(),
+                        params: decoded_params(&error.inputs, tokens),
+                    })
+                })
+        };
+
+        match decoded {
+            Some(revert) => revert,
+            None if frame.output.is_empty() => DecodedRevert::Error(
+                frame
+                    .revert_reason
+                    .as_ref()
+                    .or(frame.error.as_ref())
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            None => DecodedRevert::Raw(frame.output.clone()),
+        }
+    }
+}
+
+fn decode_function(abi: &Abi, frame: &CallFrame) -> Option<DecodedFunction> {
+    let selector = frame.selector()?;
+    let function = abi
+        .functions()
+        .find(|function| function.short_signature() == selector)?;
+    let inputs = function.decode_input(frame.input.get(4..)?).ok()?;
+    let outputs = if frame.is_reverted() {
+        None
+    } else {
+        function.decode_output(&frame.output).ok()
+    };
+
+    Some(DecodedFunction {
+        name: function.name.clone(),
+        signature: signature(function),
+        inputs: decoded_params(&function.inputs, inputs),
+        outputs: outputs.map(|outputs| decoded_params(&function.outputs, outputs)),
+    })
+}
+
+fn signature(function: &Function) -> String {
+    let params: Vec<String> = function
+        .inputs
+        .iter()
+        .map(|param| param.kind.to_string())
+        .collect();
+    format!("{}({})", function.name, params.join(","))
+}
+
+fn decoded_params(params: &[Param], tokens: Vec<Token>) -> Vec<DecodedParam> {
+    params
+        .iter()
+        .zip(tokens)
+        .map(|(param, value)| DecodedParam {
+            name: param.name.clone(),
+            value,
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedParam {
+    pub name: String,
+    pub value: Token,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedFunction {
+    pub name: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`.
+    pub signature: String,
+    pub inputs: Vec<DecodedParam>,
+    /// `None` when the call reverted or its return data could not be decoded.
+    pub outputs: Option<Vec<DecodedParam>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodedRevert {
+    /// `Error(string)`, or the revert reason or error reported by the node when there is no
+    /// revert data.
+    Error(String),
+    /// `Panic(uint256)`.
+    Panic(U256),
+    /// A custom error declared in one of the registered ABIs.
+    Custom {
+        name: String,
+        params: Vec<DecodedParam>,
+    },
+    /// Revert data that could not be decoded.
+    Raw(Bytes),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedCallFrame {
+    pub call_type: CallType,
+    pub from: Address,
+    /// `None` when the trace has no callee, e.g. for failed contract creations.
+    pub to: Option<Address>,
+    /// Name of the registered contract at `to`, if any.
+    pub contract: Option<String>,
+    pub value: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    /// `None` when no registered ABI matches the call.
+    pub function: Option<DecodedFunction>,
+    /// `Some` when the call reverted.
+    pub revert: Option<DecodedRevert>,
+    pub calls: Vec<DecodedCallFrame>,
+}
+
+impl DecodedCallFrame {
+    fn fmt_at_depth(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let call_type = format!("{:?}", self.call_type).to_uppercase();
+        write!(f, "{:indent$}{call_type} ", "", indent = depth * 2)?;
+        match (&self.contract, self.to) {
+            (Some(name), Some(to)) => write!(f, "{name}({to:?})")?,
+            (None, Some(to)) => write!(f, "{to:?}")?,
+            (_, None) => write!(f, "<unknown>")?,
+        }
+
+        match &self.function {
+            Some(function) => write!(f, "::{}({})", function.name, Params(&function.inputs))?,
+            None if !self.input.is_empty() => write!(f, "::{}", self.input)?,
+            None => {}
+        }
+        if !self.value.is_zero() {
+            write!(f, " {{value: {}}}", self.value)?;
+        }
+        if let Some(outputs) = self
+            .function
+            .as_ref()
+            .and_then(|function| function.outputs.as_ref())
+            .filter(|outputs| !outputs.is_empty())
+        {
+            write!(f, " -> ({})", Params(outputs))?;
+        }
+        match &self.revert {
+            Some(DecodedRevert::Error(reason)) => write!(f, " reverted: Error({reason:?})")?,
+            Some(DecodedRevert::Panic(code)) => write!(f, " reverted: Panic({code:#x})")?,
+            Some(DecodedRevert::Custom { name, params }) => {
+                write!(f, " reverted: {name}({})", Params(params))?;
+            }
+            Some(DecodedRevert::Raw(data)) => write!(f, " reverted: {data}")?,
+            None => {}
+        }
+        writeln!(f, " [gas used: {}]", self.gas_used)?;
+
+        for call in &self.calls {
+            call.fmt_at_depth(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DecodedCallFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_at_depth(f, 0)
+    }
+}
+
+struct Params<'a>(&'a [DecodedParam]);
+
+impl fmt::Display for Params<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, param) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            if !param.name.is_empty() {
+                write!(f, "{}: ", param.name)?;
+            }
+            write!(f, "{}", TokenDisplay(&param.value))?;
+        }
+        Ok(())
+    }
+}
+
+struct TokenDisplay<'a>(&'a Token);
+
+impl fmt::Display for TokenDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Token::Address(address) => write!(f, "{address:?}"),
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => {
+                write!(f, "0x{}", ethers::utils::hex::encode(bytes))
+            }
+            Token::Uint(value) => write!(f, "{value}"),
+            Token::Int(value) => write!(f, "{}", I256::from_raw(*value)),
+            Token::Bool(value) => write!(f, "{value}"),
+            Token::String(value) => write!(f, "{value:?}"),
+            Token::Array(tokens) | Token::FixedArray(tokens) => {
+                write!(f, "[")?;
+                write_tokens(f, tokens)?;
+                write!(f, "]")
+            }
+            Token::Tuple(tokens) => {
+                write!(f, "(")?;
+                write_tokens(f, tokens)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn write_tokens(f: &mut fmt::Formatter<'_>, tokens: &[Token]) -> fmt::Result {
+    for (index, token) in tokens.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", TokenDisplay(token))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use super::{DecodedCallFrame, TraceDecoder};
+use crate::zks_provider::types::{CallFrame, CallType};
+use ethers::types::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Gas used by a contract or a function across all of its calls.
+///
+/// Inclusive gas is the `gas_used` of the calls themselves, exclusive gas excludes the gas
+/// used by their sub-calls. Recursive calls are counted once per frame, so the inclusive gas
+/// of a contract calling itself may exceed the total gas used.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasUsage {
+    pub calls: u64,
+    pub inclusive: U256,
+    pub exclusive: U256,
+}
+
+impl GasUsage {
+    fn add(&mut self, inclusive: U256, exclusive: U256) {
+        self.calls += 1;
+        self.inclusive = self.inclusive.saturating_add(inclusive);
+        self.exclusive = self.exclusive.saturating_add(exclusive);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractGasUsage {
+    /// `None` for frames without a callee, e.g. failed contract creations.
+    pub address: Option<Address>,
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub gas: GasUsage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionGasUsage {
+    pub contract: Option<Address>,
+    /// `None` for contract creations and calls without calldata.
+    pub selector: Option<Bytes>,
+    /// Signature of the function when its contract ABI is registered in the decoder.
+    pub function: Option<String>,
+    #[serde(flatten)]
+    pub gas: GasUsage,
+}
+
+/// Gas attribution report of one or more traces. Contracts and functions are sorted by
+/// decreasing inclusive gas.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasProfile {
+    pub total_gas_used: U256,
+    pub contracts: Vec<ContractGasUsage>,
+    pub functions: Vec<FunctionGasUsage>,
+    #[serde(skip)]
+    stacks: BTreeMap<String, U256>,
+}
+
+impl GasProfile {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Exports the exclusive gas of every call stack in the folded format understood by
+    /// flamegraph tools (`frame;frame;frame gas`, one stack per line).
+    pub fn to_folded_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .filter(|(_, gas)| !gas.is_zero())
+            .map(|(stack, gas)| format!("{stack} {gas}\n"))
+            .collect()
+    }
+}
+
+/// Builds [`GasProfile`]s from the traces returned by `debug_trace_transaction`,
+/// `debug_trace_call` and `debug_trace_block_by_*`.
+#[derive(Clone, Debug, Default)]
+pub struct GasProfiler {
+    decoder: TraceDecoder,
+}
+
+#[derive(Default)]
+struct GasProfileBuilder {
+    total_gas_used: U256,
+    contracts: HashMap<Option<Address>, ContractGasUsage>,
+    functions: HashMap<(Option<Address>, Option<Bytes>), FunctionGasUsage>,
+    stacks: BTreeMap<String, U256>,
+}
+
+impl GasProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the decoder used to name contracts and functions in the report.
+    pub fn decoder(mut self, decoder: TraceDecoder) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
+    pub fn profile(&self, trace: &CallFrame) -> GasProfile {
+        self.profile_traces(std::slice::from_ref(trace))
+    }
+
+    /// Profiles several traces together, e.g. the per-transaction traces of a block.
+    pub fn profile_traces(&self, traces: &[CallFrame]) -> GasProfile {
+        let mut builder = GasProfileBuilder::default();
+        for trace in traces {
+            let frame = self.decoder.decode(trace);
+            builder.total_gas_used = builder.total_gas_used.saturating_add(frame.gas_used);
+            builder.add_frame(&frame, "");
+        }
+
+        let mut contracts: Vec<ContractGasUsage> = builder.contracts.into_values().collect();
+        contracts.sort_by(|a, b| {
+            b.gas
+                .inclusive
+                .cmp(&a.gas.inclusive)
+                .then(a.address.cmp(&b.address))
+        });
+        let mut functions: Vec<FunctionGasUsage> = builder.functions.into_values().collect();
+        functions.sort_by(|a, b| {
+            b.gas
+                .inclusive
+                .cmp(&a.gas.inclusive)
+                .then(a.contract.cmp(&b.contract))
+                .then(a.selector.cmp(&b.selector))
+        });
+
+        GasProfile {
+            total_gas_used: builder.total_gas_used,
+            contracts,
+            functions,
+            stacks: builder.stacks,
+        }
+    }
+}
+
+impl GasProfileBuilder {
+    fn add_frame(&mut self, frame: &DecodedCallFrame, parent_stack: &str) {
+        let callees_gas_used = frame.calls.iter().fold(U256::zero(), |total, call| {
+            total.saturating_add(call.gas_used)
+        });
+        let exclusive = frame.gas_used.saturating_sub(callees_gas_used);
+        let selector = match frame.call_type {
+            CallType::Create | CallType::Create2 => None,
+            _ => frame
+                .input
+                .get(..4)
+                .map(|selector| selector.to_vec().into()),
+        };
+
+        self.contracts
+            .entry(frame.to)
+            .or_insert_with(|| ContractGasUsage {
+                address: frame.to,
+                name: frame.contract.clone(),
+                gas: GasUsage::default(),
+            })
+            .gas
+            .add(frame.gas_used, exclusive);
+        self.functions
+            .entry((frame.to, selector.clone()))
+            .or_insert_with(|| FunctionGasUsage {
+                contract: frame.to,
+                selector: selector.clone(),
+                function: frame
+                    .function
+                    .as_ref()
+                    .map(|function| function.signature.clone()),
+                gas: GasUsage::default(),
+            })
+            .gas
+            .add(frame.gas_used, exclusive);
+
+        let stack = if parent_stack.is_empty() {
+            stack_frame(frame, selector.as_ref())
+        } else {
+            format!("{parent_stack};{}", stack_frame(frame, selector.as_ref()))
+        };
+        let stack_gas = self.stacks.entry(stack.clone()).or_default();
+        *stack_gas = stack_gas.saturating_add(exclusive);
+
+        for call in &frame.calls {
+            self.add_frame(call, &stack);
+        }
+    }
+}
+
+/// Label of a frame in a folded stack. Whitespace and semicolons separate stacks and frames in
+/// that format, so they are replaced with underscores, e.g. in names given to registered ABIs.
+fn stack_frame(frame: &DecodedCallFrame, selector: Option<&Bytes>) -> String {
+    let contract = match &frame.contract {
+        Some(name) => name.clone(),
+        None => match frame.to {
+            Some(to) => format!("{to:?}"),
+            None => "<unknown>".to_owned(),
+        },
+    };
+    let function = match (&frame.function, selector) {
+        (Some(function), _) => function.name.clone(),
+        (None, Some(selector)) => selector.to_string(),
+        (None, None) => format!("{:?}", frame.call_type).to_uppercase(),
+    };
+    format!("{contract}::{function}")
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || c == ';' {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
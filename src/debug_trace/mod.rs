@@ -0,0 +1,4 @@
+mod decoder;
+pub use decoder::{DecodedCallFrame, DecodedFunction, DecodedParam, DecodedRevert, TraceDecoder};
+mod gas_profiler;
+pub use gas_profiler::{ContractGasUsage, FunctionGasUsage, GasProfile, GasProfiler, GasUsage};
@@ -1,8 +1,8 @@
-use super::{rlp_append_option, PaymasterParams};
+use super::{bytes_as_array, rlp_append_option, PaymasterParams};
 use crate::zks_utils::DEFAULT_GAS_PER_PUBDATA_LIMIT;
 use ethers::{
     types::{Bytes, U256},
-    utils::rlp::Encodable,
+    utils::rlp::{DecoderError, Encodable, Rlp},
 };
 use serde::{Deserialize, Serialize};
 
@@ -11,7 +11,11 @@ use serde::{Deserialize, Serialize};
 pub struct Eip712Meta {
     pub gas_per_pubdata: U256,
     pub factory_deps: Vec<Vec<u8>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bytes_as_array"
+    )]
     pub custom_signature: Option<Bytes>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub paymaster_params: Option<PaymasterParams>,
@@ -79,6 +83,32 @@ impl Encodable for Eip712Meta {
         // 14
         rlp_append_option(stream, self.custom_signature.clone().map(|v| v.to_vec()));
         // 15
-        self.paymaster_params.rlp_append(stream);
+        if let Some(paymaster_params) = &self.paymaster_params {
+            paymaster_params.rlp_append(stream);
+        } else {
+            stream.begin_list(0);
+        }
+    }
+}
+
+impl Eip712Meta {
+    /// Decodes the meta fields of a transaction list, which start at the given `offset`.
+    pub(crate) fn rlp_decode(rlp: &Rlp, offset: usize) -> Result<Self, DecoderError> {
+        let custom_signature: Vec<u8> = rlp.val_at(offset + 2)?;
+        let paymaster_params = rlp.at(offset + 3)?;
+        Ok(Self {
+            // 12
+            gas_per_pubdata: rlp.val_at(offset)?,
+            // 13
+            factory_deps: rlp.list_at(offset + 1)?,
+            // 14
+            custom_signature: (!custom_signature.is_empty()).then(|| custom_signature.into()),
+            // 15
+            paymaster_params: if paymaster_params.is_empty() {
+                None
+            } else {
+                Some(paymaster_params.as_val()?)
+            },
+        })
     }
 }
@@ -14,6 +14,9 @@ pub use transaction_request::Eip712TransactionRequest;
 mod transaction;
 pub use transaction::Eip712Transaction;
 
+mod signer;
+pub use signer::{Eip712TransactionSigner, TypedDataSigner};
+
 mod paymaster_params;
 pub use paymaster_params::PaymasterParams;
 
@@ -49,6 +52,42 @@ pub fn hash_bytecode(bytecode: &[u8]) -> Result<[u8; 32], Eip712Error> {
     Ok(contract_hash)
 }
 
+/// zkSync nodes expect the byte fields of the EIP-712 meta (custom signature, paymaster input)
+/// as arrays of numbers rather than hex strings. Both forms are accepted when deserializing.
+pub(crate) mod bytes_as_array {
+    use ethers::types::Bytes;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BytesRepr {
+        Array(Vec<u8>),
+        Hex(Bytes),
+    }
+
+    pub(crate) fn serialize<S>(bytes: &Option<Bytes>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        bytes
+            .as_ref()
+            .map(|bytes| bytes.to_vec())
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<Bytes>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(
+            Option::<BytesRepr>::deserialize(deserializer)?.map(|bytes| match bytes {
+                BytesRepr::Array(bytes) => bytes.into(),
+                BytesRepr::Hex(bytes) => bytes,
+            }),
+        )
+    }
+}
+
 pub(crate) fn rlp_append_option<T>(stream: &mut RlpStream, value: Option<T>)
 where
     T: Encodable,
@@ -1,7 +1,9 @@
-use super::rlp_append_option;
+use super::{bytes_as_array, rlp_append_option};
+use crate::abi;
 use ethers::{
-    types::{Address, Bytes},
-    utils::rlp::Encodable,
+    abi::{Error as AbiError, Token},
+    types::{Address, Bytes, U256},
+    utils::rlp::{Decodable, DecoderError, Encodable, Rlp},
 };
 use serde::Serialize;
 
@@ -9,10 +11,42 @@ use serde::Serialize;
 #[serde(rename_all(serialize = "camelCase", deserialize = "camelCase"))]
 pub struct PaymasterParams {
     pub paymaster: Option<Address>,
+    #[serde(default, with = "bytes_as_array")]
     pub paymaster_input: Option<Bytes>,
 }
 
 impl PaymasterParams {
+    /// Params for the `general` paymaster flow, where the paymaster pays the fee without
+    /// requiring anything from the sender but the `inner_input` it interprets.
+    pub fn general(paymaster: Address, inner_input: Bytes) -> Result<Self, AbiError> {
+        let paymaster_input = abi::paymaster_flow_contract()?
+            .function("general")?
+            .encode_input(&[Token::Bytes(inner_input.to_vec())])?;
+        Ok(Self::default()
+            .paymaster(paymaster)
+            .paymaster_input(paymaster_input.into()))
+    }
+
+    /// Params for the `approvalBased` paymaster flow, where the paymaster takes the fee in
+    /// `token` and the sender approves it for at least `min_allowance` before validation.
+    pub fn approval_based(
+        paymaster: Address,
+        token: Address,
+        min_allowance: U256,
+        inner_input: Bytes,
+    ) -> Result<Self, AbiError> {
+        let paymaster_input = abi::paymaster_flow_contract()?
+            .function("approvalBased")?
+            .encode_input(&[
+                Token::Address(token),
+                Token::Uint(min_allowance),
+                Token::Bytes(inner_input.to_vec()),
+            ])?;
+        Ok(Self::default()
+            .paymaster(paymaster)
+            .paymaster_input(paymaster_input.into()))
+    }
+
     pub fn paymaster(mut self, paymaster: Address) -> Self {
         self.paymaster = Some(paymaster);
         self
@@ -31,3 +65,13 @@ impl Encodable for PaymasterParams {
         rlp_append_option(stream, self.paymaster_input.clone().map(|v| v.to_vec()));
     }
 }
+
+impl Decodable for PaymasterParams {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        let paymaster_input: Vec<u8> = rlp.val_at(1)?;
+        Ok(Self {
+            paymaster: Some(rlp.val_at(0)?),
+            paymaster_input: Some(paymaster_input.into()),
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use super::Eip712Transaction;
+use async_trait::async_trait;
+use ethers::{
+    core::k256::ecdsa::{
+        signature::hazmat::PrehashSigner, RecoveryId, Signature as RecoverableSignature,
+    },
+    signers::{Signer, Wallet},
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature,
+    },
+};
+
+/// Signers of zkSync EIP-712 transactions.
+///
+/// Signers holding a key sign the transaction's digest with the provided method. Signers that
+/// need the typed data itself, like the ones behind `eth_signTypedData_v4`, override it using
+/// [`Eip712Transaction::typed_data`]. Any other ethers [`Signer`] can be used through
+/// [`TypedDataSigner`].
+#[async_trait]
+pub trait Eip712TransactionSigner: Signer {
+    async fn sign_transaction_eip712(
+        &self,
+        transaction: &Eip712Transaction,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_typed_data(transaction).await
+    }
+}
+
+impl<D> Eip712TransactionSigner for Wallet<D> where
+    D: Sync + Send + PrehashSigner<(RecoverableSignature, RecoveryId)>
+{
+}
+
+/// Signs zkSync transactions with any ethers [`Signer`] that signs typed data from its digest,
+/// e.g. `Ledger`, `Trezor` or `AwsSigner`, which this crate cannot implement
+/// [`Eip712TransactionSigner`] for.
+#[derive(Clone, Debug)]
+pub struct TypedDataSigner<S>(pub S);
+
+#[async_trait]
+impl<S: Signer> Signer for TypedDataSigner<S> {
+    type Error = S::Error;
+
+    async fn sign_message<M: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: M,
+    ) -> Result<Signature, Self::Error> {
+        self.0.sign_message(message).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        self.0.sign_transaction(tx).await
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        self.0.sign_typed_data(payload).await
+    }
+
+    fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.0.chain_id()
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        Self(self.0.with_chain_id(chain_id))
+    }
+}
+
+impl<S: Signer> Eip712TransactionSigner for TypedDataSigner<S> {}
@@ -4,14 +4,15 @@ use ethers::{
     abi::encode,
     types::{
         transaction::eip712::{
-            encode_data, encode_type, EIP712Domain, Eip712, Eip712DomainType, Eip712Error, Types,
+            encode_data, encode_type, EIP712Domain, Eip712, Eip712DomainType, Eip712Error,
+            TypedData, Types,
         },
-        Address, Bytes, U256,
+        Address, Bytes, H256, U256,
     },
     utils::keccak256,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all(serialize = "camelCase", deserialize = "camelCase"))]
@@ -151,6 +152,62 @@ impl Eip712Transaction {
     }
 }
 
+impl Eip712Transaction {
+    /// Returns the EIP-712 digest that is signed by the initiator of the transaction.
+    pub fn signing_digest(&self) -> Result<H256, Eip712Error> {
+        self.encode_eip712().map(H256)
+    }
+
+    /// Returns the hash under which the node indexes the transaction once it is signed with
+    /// `signature`, i.e. `keccak256(signing_digest ++ keccak256(signature))`.
+    pub fn tx_hash(&self, signature: &[u8]) -> Result<H256, Eip712Error> {
+        let digest = self.signing_digest()?;
+        Ok(H256(keccak256(
+            [digest.as_bytes(), &keccak256(signature)].concat(),
+        )))
+    }
+
+    /// Returns the transaction as EIP-712 typed data, the payload of `eth_signTypedData_v4`.
+    pub fn typed_data(&self) -> Result<TypedData, Eip712Error> {
+        let mut types = eip712_transaction_types();
+        types.insert(
+            "EIP712Domain".to_owned(),
+            vec![
+                Eip712DomainType {
+                    name: "name".to_owned(),
+                    r#type: "string".to_owned(),
+                },
+                Eip712DomainType {
+                    name: "version".to_owned(),
+                    r#type: "string".to_owned(),
+                },
+                Eip712DomainType {
+                    name: "chainId".to_owned(),
+                    r#type: "uint256".to_owned(),
+                },
+            ],
+        );
+        let message = match json!(self) {
+            Value::Object(fields) => fields
+                .into_iter()
+                .filter(|(name, _)| name != "chainId")
+                .collect(),
+            _ => {
+                return Err(Eip712Error::Message(
+                    "Transaction is not serialized as an object".to_owned(),
+                ))
+            }
+        };
+
+        Ok(TypedData {
+            domain: self.domain()?,
+            types,
+            primary_type: "Transaction".to_owned(),
+            message,
+        })
+    }
+}
+
 impl Default for Eip712Transaction {
     fn default() -> Self {
         Self {
@@ -278,6 +335,7 @@ impl TryFrom<Eip712TransactionRequest> for Eip712Transaction {
             .nonce(tx.nonce)
             .value(tx.value)
             .data(tx.data)
+            .gas_per_pubdata_byte_limit(tx.custom_data.gas_per_pubdata)
             .factory_deps(
                 tx.custom_data
                     .factory_deps
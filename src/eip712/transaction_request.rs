@@ -1,19 +1,24 @@
 use std::{fs::File, io::BufReader, path::PathBuf, str::FromStr};
 
-use super::{hash_bytecode, rlp_append_option, Eip712Meta};
+use super::{hash_bytecode, rlp_append_option, Eip712Meta, Eip712Transaction};
 use crate::{
+    abi,
     zks_utils::{
-        self, CONTRACT_DEPLOYER_ADDR, EIP712_TX_TYPE, ERA_CHAIN_ID, MAX_PRIORITY_FEE_PER_GAS,
+        self, is_eth, CONTRACT_DEPLOYER_ADDR, EIP712_TX_TYPE, ERA_CHAIN_ID,
+        MAX_PRIORITY_FEE_PER_GAS,
+    },
+    zks_wallet::{
+        AccountAbstractionVersion, CallRequest, DeployRequest, Overrides, TransferRequest,
+        WithdrawRequest, ZKRequestError,
     },
-    zks_wallet::{DeployRequest, Overrides, TransferRequest, WithdrawRequest, ZKRequestError},
 };
 use ethers::{
-    abi::{Abi, HumanReadableParser, ParseError},
+    abi::{Abi, HumanReadableParser, ParseError, Token},
     types::{
         transaction::{eip2930::AccessList, eip712::Eip712Error},
-        Address, Bytes, Signature, U256,
+        Address, Bytes, Eip1559TransactionRequest, Signature, H256, U256,
     },
-    utils::rlp::{Encodable, RlpStream},
+    utils::rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream},
 };
 use ethers_contract::encode_function_data;
 use serde::{Deserialize, Serialize};
@@ -58,6 +63,9 @@ impl Eip712TransactionRequest {
         if let Some(value) = overrides.value {
             tx.value = value;
         }
+        if let Some(paymaster_params) = overrides.paymaster_params {
+            tx.custom_data = tx.custom_data.paymaster_params(paymaster_params);
+        }
         tx
     }
 
@@ -216,6 +224,102 @@ impl Eip712TransactionRequest {
         stream.finalize_unbounded_list();
         Ok(stream.out().freeze().into())
     }
+
+    /// Decodes a raw EIP-712 transaction, as sent through `eth_sendRawTransaction`. The
+    /// leading transaction type byte is optional.
+    pub fn decode(raw: &[u8]) -> Result<Self, Eip712Error> {
+        let raw = match raw.split_first() {
+            Some((&EIP712_TX_TYPE, rlp)) => rlp,
+            _ => raw,
+        };
+        Self::decode_rlp(&Rlp::new(raw))
+            .map_err(|e| Eip712Error::Message(format!("Error in the rlp decoding {e}")))
+    }
+
+    /// Decodes a raw EIP-712 transaction signed by an EOA, returning the ECDSA signature
+    /// stored in its custom signature.
+    pub fn decode_signed(raw: &[u8]) -> Result<(Self, Signature), Eip712Error> {
+        let request = Self::decode(raw)?;
+        let signature = request.signature()?;
+        Ok((request, signature))
+    }
+
+    fn decode_rlp(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 16 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        let data: Vec<u8> = rlp.val_at(6)?;
+        Ok(Self {
+            // 0
+            nonce: rlp.val_at(0)?,
+            // 1
+            max_priority_fee_per_gas: rlp.val_at(1)?,
+            // 2
+            max_fee_per_gas: decode_option(rlp, 2)?,
+            // 3
+            gas_limit: decode_option(rlp, 3)?,
+            // 4
+            to: rlp.val_at(4)?,
+            // 5
+            value: rlp.val_at(5)?,
+            // 6
+            data: data.into(),
+            // 7, 8 and 9 are the chain id and an empty signature, 10 is the chain id again.
+            chain_id: rlp.val_at(10)?,
+            // 11
+            from: rlp.val_at(11)?,
+            // 12, 13, 14, 15
+            custom_data: Eip712Meta::rlp_decode(rlp, 12)?,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the ECDSA signature stored in the custom signature of the transaction.
+    pub fn signature(&self) -> Result<Signature, Eip712Error> {
+        let custom_signature = self
+            .custom_data
+            .custom_signature
+            .as_ref()
+            .ok_or(Eip712Error::Message("No signature provided".to_owned()))?;
+        Signature::try_from(custom_signature.as_ref())
+            .map_err(|e| Eip712Error::Message(format!("Invalid signature: {e}")))
+    }
+
+    /// Recovers the address that signed the EIP-712 digest of the transaction.
+    pub fn recover_signer(&self) -> Result<Address, Eip712Error> {
+        let signature = self.signature()?;
+        signature
+            .recover(self.signing_digest()?)
+            .map_err(|e| Eip712Error::Message(format!("Error recovering signer: {e}")))
+    }
+
+    /// Returns the EIP-712 digest to be signed. The gas limit and max fee per gas must be set.
+    pub fn signing_digest(&self) -> Result<H256, Eip712Error> {
+        Eip712Transaction::try_from(self.clone())?.signing_digest()
+    }
+
+    /// Returns the hash the transaction will have on L2 once broadcast. The custom signature
+    /// must be set.
+    pub fn tx_hash(&self) -> Result<H256, Eip712Error> {
+        let custom_signature = self
+            .custom_data
+            .custom_signature
+            .as_ref()
+            .ok_or(Eip712Error::Message("No signature provided".to_owned()))?;
+        Eip712Transaction::try_from(self.clone())?.tx_hash(custom_signature)
+    }
+}
+
+fn decode_option<T>(rlp: &Rlp, index: usize) -> Result<Option<T>, DecoderError>
+where
+    T: Decodable,
+{
+    let item = rlp.at(index)?;
+    if item.is_empty() {
+        Ok(None)
+    } else {
+        item.as_val().map(Some)
+    }
 }
 
 impl Default for Eip712TransactionRequest {
@@ -244,35 +348,82 @@ impl TryFrom<WithdrawRequest> for Eip712TransactionRequest {
     type Error = ZKRequestError;
 
     fn try_from(request: WithdrawRequest) -> Result<Self, Self::Error> {
-        let contract_address =
-            Address::from_str(zks_utils::CONTRACTS_L2_ETH_TOKEN_ADDR).map_err(|e| {
-                ZKRequestError::CustomError(format!("Error getting L2 ETH token address {e:?}"))
-            })?;
-        let function_signature = "function withdraw(address _l1Receiver) external payable override";
-        let function = HumanReadableParser::parse_function(function_signature)
-            .map_err(ParseError::LexerError)?;
-        let function_args = function.decode_input(&zks_utils::encode_args(
-            &function,
-            &[format!("{:?}", request.to)],
-        )?)?;
-        let data: Bytes = function.encode_input(&function_args)?.into();
+        let (contract_address, value, data) = match request.token.filter(|token| !is_eth(*token)) {
+            Some(token) => {
+                let bridge = request.bridge.ok_or(ZKRequestError::CustomError(
+                    "A bridge is needed to withdraw ERC-20 tokens".to_owned(),
+                ))?;
+                let data = abi::l2_bridge_contract()?
+                    .function("withdraw")?
+                    .encode_input(&[
+                        Token::Address(request.to),
+                        Token::Address(token),
+                        Token::Uint(request.amount),
+                    ])?;
+                (bridge, U256::zero(), data.into())
+            }
+            None => {
+                let contract_address = Address::from_str(zks_utils::CONTRACTS_L2_ETH_TOKEN_ADDR)
+                    .map_err(|e| {
+                        ZKRequestError::CustomError(format!(
+                            "Error getting L2 ETH token address {e:?}"
+                        ))
+                    })?;
+                let function_signature =
+                    "function withdraw(address _l1Receiver) external payable override";
+                let function = HumanReadableParser::parse_function(function_signature)
+                    .map_err(ParseError::LexerError)?;
+                let function_args = function.decode_input(&zks_utils::encode_args(
+                    &function,
+                    &[format!("{:?}", request.to)],
+                )?)?;
+                let data: Bytes = function.encode_input(&function_args)?.into();
+                (contract_address, request.amount, data)
+            }
+        };
+
+        let mut custom_data = Eip712Meta::new();
+        if let Some(paymaster_params) = request.paymaster_params {
+            custom_data = custom_data.paymaster_params(paymaster_params);
+        }
 
         Ok(Eip712TransactionRequest::new()
             .r#type(EIP712_TX_TYPE)
             .to(contract_address)
-            .value(request.amount)
+            .value(value)
             .from(request.from)
-            .data(data))
+            .data(data)
+            .custom_data(custom_data))
     }
 }
 
 impl From<TransferRequest> for Eip712TransactionRequest {
     fn from(request: TransferRequest) -> Self {
+        let (to, value, data) = request.call();
+        let mut custom_data = Eip712Meta::new();
+        if let Some(paymaster_params) = request.paymaster_params {
+            custom_data = custom_data.paymaster_params(paymaster_params);
+        }
+
         Eip712TransactionRequest::new()
             .r#type(EIP712_TX_TYPE)
-            .to(request.to)
-            .value(request.amount)
+            .to(to)
+            .value(value)
+            .data(data)
             .from(request.from)
+            .custom_data(custom_data)
+    }
+}
+
+impl TryFrom<CallRequest> for Eip712TransactionRequest {
+    type Error = ZKRequestError;
+
+    fn try_from(request: CallRequest) -> Result<Self, Self::Error> {
+        let call: Eip1559TransactionRequest = request.clone().try_into()?;
+        Ok(Eip712TransactionRequest::new()
+            .r#type(EIP712_TX_TYPE)
+            .to(request.to)
+            .data(call.data.unwrap_or_default()))
     }
 }
 
@@ -283,7 +434,7 @@ impl TryFrom<DeployRequest> for Eip712TransactionRequest {
         let mut contract_deployer_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         contract_deployer_path.push("src/abi/ContractDeployer.json");
 
-        let custom_data = Eip712Meta::new().factory_deps({
+        let mut custom_data = Eip712Meta::new().factory_deps({
             let mut factory_deps = Vec::new();
             if let Some(factory_dependencies) = request.factory_deps {
                 factory_deps.extend(factory_dependencies);
@@ -291,6 +442,9 @@ impl TryFrom<DeployRequest> for Eip712TransactionRequest {
             factory_deps.push(request.contract_bytecode.clone());
             factory_deps
         });
+        if let Some(paymaster_params) = request.paymaster_params {
+            custom_data = custom_data.paymaster_params(paymaster_params);
+        }
 
         let contract_deployer = Abi::load(BufReader::new(
             File::open(contract_deployer_path).map_err(|e| {
@@ -299,10 +453,6 @@ impl TryFrom<DeployRequest> for Eip712TransactionRequest {
                 ))
             })?,
         ))?;
-        let create = contract_deployer.function("create")?;
-
-        // TODO: User could provide this instead of defaulting.
-        let salt = [0_u8; 32];
         let bytecode_hash = hash_bytecode(&request.contract_bytecode).map_err(|e| {
             ZKRequestError::CustomError(format!("Error hashing contract bytecode {e:?}"))
         })?;
@@ -322,7 +472,25 @@ impl TryFrom<DeployRequest> for Eip712TransactionRequest {
             }
         };
 
-        let data = encode_function_data(create, (salt, bytecode_hash, call_data))?;
+        let salt = request.salt.unwrap_or_default().0;
+        let data = match (request.account_abstraction_version, request.salt) {
+            (AccountAbstractionVersion::None, None) => encode_function_data(
+                contract_deployer.function("create")?,
+                (salt, bytecode_hash, call_data),
+            )?,
+            (AccountAbstractionVersion::None, Some(_)) => encode_function_data(
+                contract_deployer.function("create2")?,
+                (salt, bytecode_hash, call_data),
+            )?,
+            (version, None) => encode_function_data(
+                contract_deployer.function("createAccount")?,
+                (salt, bytecode_hash, call_data, u8::from(version)),
+            )?,
+            (version, Some(_)) => encode_function_data(
+                contract_deployer.function("create2Account")?,
+                (salt, bytecode_hash, call_data, u8::from(version)),
+            )?,
+        };
 
         let contract_deployer_address = Address::from_str(CONTRACT_DEPLOYER_ADDR).map_err(|e| {
             ZKRequestError::CustomError(format!("Error getting contract deployer address {e:?}"))
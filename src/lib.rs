@@ -46,7 +46,12 @@ pub mod abi;
 pub use ethers::*;
 
 pub mod contracts;
+pub mod debug_trace;
 pub mod eip712;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod remote_signer;
+pub mod verifying_paymaster;
 pub mod zks_provider;
 pub mod zks_utils;
 pub mod zks_wallet;
@@ -0,0 +1,32 @@
+use ethers::providers::{JsonRpcError, ProviderError, RpcError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum MockNodeError {
+    #[error("Unsupported method: {0}")]
+    UnsupportedMethod(String),
+    #[error("Invalid params for {method}: {reason}")]
+    InvalidParams { method: String, reason: String },
+    #[error("Serialization error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("{0}")]
+    CustomError(String),
+}
+
+impl RpcError for MockNodeError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        None
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            MockNodeError::SerdeJson(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<MockNodeError> for ProviderError {
+    fn from(error: MockNodeError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(error))
+    }
+}
@@ -0,0 +1,5 @@
+mod errors;
+pub use errors::MockNodeError;
+
+mod node;
+pub use node::{MockZKSNode, ScriptedReceipt, SentTransaction};
@@ -0,0 +1,714 @@
+use super::MockNodeError;
+use crate::{
+    eip712::Eip712TransactionRequest,
+    zks_provider::types::{
+        BridgeContracts, CallFrame, Fee, L2ToL1Log, Proof, ZksTransactionReceipt,
+    },
+    zks_utils::{EIP712_TX_TYPE, ETHER_L1_ADDRESS},
+};
+use async_trait::async_trait;
+use ethers::{
+    providers::{JsonRpcClient, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Block, Bytes, FeeHistory, Log,
+        Transaction, TransactionReceipt, H256, U256, U64,
+    },
+    utils::{keccak256, rlp::Rlp},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+const DEFAULT_GAS_PRICE: u64 = 250_000_000;
+const DEFAULT_MAX_PRIORITY_FEE_PER_GAS: u64 = 0;
+const DEFAULT_GAS_ESTIMATE: u64 = 1_000_000;
+const DEFAULT_GAS_USED: u64 = 21_000;
+const DEFAULT_GAS_PER_PUBDATA_LIMIT: u64 = 800;
+const LEGACY_TX_TYPE: u8 = 0x0;
+const EIP2930_TX_TYPE: u8 = 0x1;
+const EIP1559_TX_TYPE: u8 = 0x2;
+const POLLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// What the node should put in the receipt of every transaction sent to a given address.
+#[derive(Clone, Debug)]
+pub struct ScriptedReceipt {
+    pub status: bool,
+    pub gas_used: U256,
+    pub contract_address: Option<Address>,
+    pub logs: Vec<Log>,
+    pub l2_to_l1_logs: Vec<L2ToL1Log>,
+}
+
+impl ScriptedReceipt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn failed(mut self) -> Self {
+        self.status = false;
+        self
+    }
+
+    pub fn gas_used<T>(mut self, gas_used: T) -> Self
+    where
+        T: Into<U256>,
+    {
+        self.gas_used = gas_used.into();
+        self
+    }
+
+    pub fn contract_address(mut self, contract_address: Address) -> Self {
+        self.contract_address = Some(contract_address);
+        self
+    }
+
+    pub fn log(mut self, log: Log) -> Self {
+        self.logs.push(log);
+        self
+    }
+
+    /// Adds an L2 to L1 log to the receipt. Its block, batch and transaction fields are
+    /// filled in by the node.
+    pub fn l2_to_l1_log(mut self, l2_to_l1_log: L2ToL1Log) -> Self {
+        self.l2_to_l1_logs.push(l2_to_l1_log);
+        self
+    }
+}
+
+impl Default for ScriptedReceipt {
+    fn default() -> Self {
+        Self {
+            status: true,
+            gas_used: DEFAULT_GAS_USED.into(),
+            contract_address: None,
+            logs: Vec::new(),
+            l2_to_l1_logs: Vec::new(),
+        }
+    }
+}
+
+/// A transaction received through `eth_sendRawTransaction`.
+#[derive(Clone, Debug)]
+pub struct SentTransaction {
+    pub hash: H256,
+    pub tx_type: u8,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub nonce: U256,
+    pub value: U256,
+    pub gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub data: Bytes,
+    pub raw: Bytes,
+}
+
+impl SentTransaction {
+    fn effective_gas_price(&self, base_fee_per_gas: U256) -> U256 {
+        match self.tx_type {
+            LEGACY_TX_TYPE | EIP2930_TX_TYPE => self.max_fee_per_gas,
+            _ => self
+                .max_fee_per_gas
+                .min(base_fee_per_gas + self.max_priority_fee_per_gas),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MockNodeState {
+    chain_id: U256,
+    l1_chain_id: U256,
+    block_number: U64,
+    l1_batch_number: U256,
+    gas_price: U256,
+    max_priority_fee_per_gas: U256,
+    gas_estimate: U256,
+    fee: Option<Fee>,
+    main_contract: Address,
+    bridge_contracts: Option<BridgeContracts>,
+    balances: HashMap<Address, U256>,
+    nonces: HashMap<Address, U256>,
+    call_responses: HashMap<(Address, [u8; 4]), Bytes>,
+    token_prices: HashMap<Address, String>,
+    scripted_receipts: HashMap<Address, ScriptedReceipt>,
+    transactions: HashMap<H256, Transaction>,
+    receipts: HashMap<H256, ZksTransactionReceipt>,
+    proofs: HashMap<H256, Proof>,
+    traces: HashMap<H256, CallFrame>,
+    call_traces: HashMap<Address, CallFrame>,
+    block_traces: HashMap<U64, Vec<CallFrame>>,
+    responses: HashMap<String, Value>,
+    sent_transactions: Vec<SentTransaction>,
+}
+
+impl MockNodeState {
+    fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            l1_chain_id: Default::default(),
+            block_number: Default::default(),
+            l1_batch_number: Default::default(),
+            gas_price: DEFAULT_GAS_PRICE.into(),
+            max_priority_fee_per_gas: DEFAULT_MAX_PRIORITY_FEE_PER_GAS.into(),
+            gas_estimate: DEFAULT_GAS_ESTIMATE.into(),
+            fee: None,
+            main_contract: Default::default(),
+            bridge_contracts: None,
+            balances: Default::default(),
+            nonces: Default::default(),
+            call_responses: Default::default(),
+            token_prices: Default::default(),
+            scripted_receipts: Default::default(),
+            transactions: Default::default(),
+            receipts: Default::default(),
+            proofs: Default::default(),
+            traces: Default::default(),
+            call_traces: Default::default(),
+            block_traces: Default::default(),
+            responses: Default::default(),
+            sent_transactions: Default::default(),
+        }
+    }
+
+    fn balance(&self, address: Address) -> U256 {
+        self.balances.get(&address).copied().unwrap_or_default()
+    }
+
+    fn nonce(&self, address: Address) -> U256 {
+        self.nonces.get(&address).copied().unwrap_or_default()
+    }
+
+    fn fee(&self) -> Fee {
+        self.fee.unwrap_or(Fee {
+            gas_limit: self.gas_estimate,
+            gas_per_pubdata_limit: DEFAULT_GAS_PER_PUBDATA_LIMIT.into(),
+            max_fee_per_gas: self.gas_price,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+        })
+    }
+
+    fn block(&self, number: U64) -> Block<H256> {
+        Block {
+            hash: Some(block_hash(number)),
+            parent_hash: block_hash(number.saturating_sub(U64::one())),
+            number: Some(number),
+            base_fee_per_gas: Some(self.gas_price),
+            transactions: self
+                .transactions
+                .values()
+                .filter(|transaction| transaction.block_number == Some(number))
+                .map(|transaction| transaction.hash)
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn fee_history(&self, block_count: U256) -> FeeHistory {
+        let block_count = block_count.low_u64().max(1);
+        let blocks = (0..block_count).collect::<Vec<u64>>();
+        FeeHistory {
+            base_fee_per_gas: (0..=block_count).map(|_| self.gas_price).collect(),
+            gas_used_ratio: blocks.iter().map(|_| 0.5_f64).collect(),
+            oldest_block: self
+                .block_number
+                .saturating_sub(U64::from(block_count))
+                .as_u64()
+                .into(),
+            reward: blocks
+                .iter()
+                .map(|_| vec![self.max_priority_fee_per_gas])
+                .collect(),
+        }
+    }
+
+    fn call(&self, request: &Value) -> Result<Bytes, MockNodeError> {
+        let to: Address = serde_json::from_value(request.get("to").cloned().unwrap_or_default())?;
+        let data: Bytes = serde_json::from_value(
+            request
+                .get("data")
+                .or_else(|| request.get("input"))
+                .cloned()
+                .unwrap_or_else(|| json!("0x")),
+        )?;
+        let selector: [u8; 4] = data
+            .get(..4)
+            .unwrap_or_default()
+            .try_into()
+            .unwrap_or_default();
+
+        self.call_responses
+            .get(&(to, selector))
+            .cloned()
+            .ok_or(MockNodeError::CustomError(format!(
+                "execution reverted: no response scripted for selector 0x{} on {to:?}",
+                hex::encode(selector)
+            )))
+    }
+
+    fn send_raw_transaction(&mut self, raw: Bytes) -> Result<H256, MockNodeError> {
+        let transaction = decode_raw_transaction(&raw)?;
+
+        let scripted_receipt = transaction
+            .to
+            .and_then(|to| self.scripted_receipts.get(&to).cloned())
+            .unwrap_or_default();
+        let effective_gas_price = transaction.effective_gas_price(self.gas_price);
+        let fee = scripted_receipt.gas_used * effective_gas_price;
+        let value = if scripted_receipt.status {
+            transaction.value
+        } else {
+            U256::zero()
+        };
+
+        let sender_balance = self.balance(transaction.from);
+        if sender_balance < value + fee {
+            return Err(MockNodeError::CustomError(
+                "insufficient funds for gas * price + value".to_owned(),
+            ));
+        }
+        self.balances
+            .insert(transaction.from, sender_balance - value - fee);
+        if let Some(to) = transaction.to {
+            let receiver_balance = self.balance(to);
+            self.balances.insert(to, receiver_balance + value);
+        }
+        let nonce = self.nonce(transaction.from);
+        self.nonces.insert(transaction.from, nonce + 1_u8);
+        self.block_number += U64::one();
+
+        let block_number = self.block_number;
+        let logs = scripted_receipt
+            .logs
+            .iter()
+            .cloned()
+            .map(|log| Log {
+                block_hash: Some(block_hash(block_number)),
+                block_number: Some(block_number),
+                transaction_hash: Some(transaction.hash),
+                transaction_index: Some(U64::zero()),
+                ..log
+            })
+            .collect();
+        let receipt = TransactionReceipt {
+            transaction_hash: transaction.hash,
+            transaction_index: U64::zero(),
+            block_hash: Some(block_hash(block_number)),
+            block_number: Some(block_number),
+            from: transaction.from,
+            to: transaction.to,
+            cumulative_gas_used: scripted_receipt.gas_used,
+            gas_used: Some(scripted_receipt.gas_used),
+            contract_address: scripted_receipt.contract_address,
+            logs,
+            status: Some(u8::from(scripted_receipt.status).into()),
+            transaction_type: Some(transaction.tx_type.into()),
+            effective_gas_price: Some(effective_gas_price),
+            ..Default::default()
+        };
+        let l1_batch_number = self.l1_batch_number.low_u64().into();
+        let l2_to_l1_logs = scripted_receipt
+            .l2_to_l1_logs
+            .into_iter()
+            .zip(0_u64..)
+            .map(|(l2_to_l1_log, log_index)| L2ToL1Log {
+                block_hash: Some(block_hash(block_number)),
+                block_number: Some(block_number),
+                l1_batch_number: Some(l1_batch_number),
+                transaction_hash: Some(transaction.hash),
+                transaction_index: Some(U64::zero()),
+                transaction_log_index: Some(log_index.into()),
+                tx_number_in_block: Some(U64::zero()),
+                log_index: log_index.into(),
+                ..l2_to_l1_log
+            })
+            .collect();
+        let receipt = ZksTransactionReceipt {
+            receipt,
+            l1_batch_number: Some(l1_batch_number),
+            l1_batch_tx_index: Some(U64::zero()),
+            l2_to_l1_logs,
+        };
+
+        self.transactions.insert(
+            transaction.hash,
+            Transaction {
+                hash: transaction.hash,
+                nonce: transaction.nonce,
+                block_hash: Some(block_hash(block_number)),
+                block_number: Some(block_number),
+                transaction_index: Some(U64::zero()),
+                from: transaction.from,
+                to: transaction.to,
+                value: transaction.value,
+                gas_price: Some(effective_gas_price),
+                gas: transaction.gas,
+                input: transaction.data.clone(),
+                transaction_type: Some(transaction.tx_type.into()),
+                max_fee_per_gas: Some(transaction.max_fee_per_gas),
+                max_priority_fee_per_gas: Some(transaction.max_priority_fee_per_gas),
+                chain_id: Some(self.chain_id),
+                ..Default::default()
+            },
+        );
+        self.receipts.insert(transaction.hash, receipt);
+        let hash = transaction.hash;
+        self.sent_transactions.push(transaction);
+
+        Ok(hash)
+    }
+}
+
+/// An in-memory zkSync node that can be plugged into an ethers `Provider`, so that
+/// `ZKSProvider` and `ZKSWallet` can be exercised without a running L1 or L2 node.
+///
+/// Accounts, balances, contract call results, receipts, L2 to L1 logs, proofs and traces
+/// are scripted through the setters below. Any method can also be answered with a fixed
+/// response through [`MockZKSNode::set_response`].
+#[derive(Clone, Debug)]
+pub struct MockZKSNode {
+    state: Arc<Mutex<MockNodeState>>,
+}
+
+impl MockZKSNode {
+    pub fn new<T>(chain_id: T) -> Self
+    where
+        T: Into<u64>,
+    {
+        Self {
+            state: Arc::new(Mutex::new(MockNodeState::new(chain_id.into()))),
+        }
+    }
+
+    /// Returns a provider backed by this node that polls pending transactions fast enough
+    /// for tests.
+    pub fn provider(&self) -> Provider<MockZKSNode> {
+        Provider::new(self.clone()).interval(POLLING_INTERVAL)
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockNodeState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn set_l1_chain_id<T>(&self, l1_chain_id: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().l1_chain_id = l1_chain_id.into();
+    }
+
+    pub fn set_balance<T>(&self, address: Address, balance: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().balances.insert(address, balance.into());
+    }
+
+    pub fn balance(&self, address: Address) -> U256 {
+        self.state().balance(address)
+    }
+
+    pub fn set_nonce<T>(&self, address: Address, nonce: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().nonces.insert(address, nonce.into());
+    }
+
+    pub fn nonce(&self, address: Address) -> U256 {
+        self.state().nonce(address)
+    }
+
+    pub fn set_gas_price<T>(&self, gas_price: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().gas_price = gas_price.into();
+    }
+
+    pub fn set_max_priority_fee_per_gas<T>(&self, max_priority_fee_per_gas: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+    }
+
+    /// Sets the value returned by `eth_estimateGas` and `zks_estimateGasL1ToL2`.
+    pub fn set_gas_estimate<T>(&self, gas_estimate: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().gas_estimate = gas_estimate.into();
+    }
+
+    /// Sets the value returned by `zks_estimateFee`. When not set, the fee is derived from
+    /// the gas estimate and the gas price.
+    pub fn set_fee(&self, fee: Fee) {
+        self.state().fee = Some(fee);
+    }
+
+    pub fn set_main_contract(&self, main_contract: Address) {
+        self.state().main_contract = main_contract;
+    }
+
+    pub fn set_bridge_contracts(&self, bridge_contracts: BridgeContracts) {
+        self.state().bridge_contracts = Some(bridge_contracts);
+    }
+
+    pub fn set_l1_batch_number<T>(&self, l1_batch_number: T)
+    where
+        T: Into<U256>,
+    {
+        self.state().l1_batch_number = l1_batch_number.into();
+    }
+
+    /// Sets the output returned by `eth_call` for calls to `to` whose calldata starts with
+    /// `selector`.
+    pub fn set_call_response<T>(&self, to: Address, selector: [u8; 4], output: T)
+    where
+        T: Into<Bytes>,
+    {
+        self.state()
+            .call_responses
+            .insert((to, selector), output.into());
+    }
+
+    /// Sets the USD price returned by `zks_getTokenPrice` for `token`, e.g. `"1800.5"`. ETH's
+    /// price is the one of the zero address.
+    pub fn set_token_price(&self, token: Address, price: &str) {
+        self.state().token_prices.insert(token, price.to_owned());
+    }
+
+    /// Sets the receipt fields used for every transaction sent to `to` from now on.
+    pub fn script_receipt(&self, to: Address, receipt: ScriptedReceipt) {
+        self.state().scripted_receipts.insert(to, receipt);
+    }
+
+    pub fn set_l2_to_l1_log_proof(&self, tx_hash: H256, proof: Proof) {
+        self.state().proofs.insert(tx_hash, proof);
+    }
+
+    /// Sets the trace returned by `debug_traceTransaction`.
+    pub fn set_transaction_trace(&self, tx_hash: H256, trace: CallFrame) {
+        self.state().traces.insert(tx_hash, trace);
+    }
+
+    /// Sets the trace returned by `debug_traceCall` for calls to `to`.
+    pub fn set_call_trace(&self, to: Address, trace: CallFrame) {
+        self.state().call_traces.insert(to, trace);
+    }
+
+    /// Sets the per-transaction traces returned by `debug_traceBlockByNumber` and
+    /// `debug_traceBlockByHash`.
+    pub fn set_block_traces<T>(&self, block_number: T, traces: Vec<CallFrame>)
+    where
+        T: Into<U64>,
+    {
+        self.state()
+            .block_traces
+            .insert(block_number.into(), traces);
+    }
+
+    /// Answers every call to `method` with `response`, bypassing the node's own handling.
+    pub fn set_response<T>(&self, method: &str, response: T) -> Result<(), MockNodeError>
+    where
+        T: Serialize,
+    {
+        self.state()
+            .responses
+            .insert(method.to_owned(), serde_json::to_value(response)?);
+        Ok(())
+    }
+
+    /// Returns the raw receipt stored for `tx_hash`, including the zkSync-specific fields.
+    pub fn receipt(&self, tx_hash: H256) -> Option<ZksTransactionReceipt> {
+        self.state().receipts.get(&tx_hash).cloned()
+    }
+
+    /// Returns every transaction received so far, in order.
+    pub fn sent_transactions(&self) -> Vec<SentTransaction> {
+        self.state().sent_transactions.clone()
+    }
+
+    fn handle(&self, method: &str, params: Value) -> Result<Value, MockNodeError> {
+        let mut state = self.state();
+        if let Some(response) = state.responses.get(method) {
+            return Ok(response.clone());
+        }
+
+        let response = match method {
+            "eth_chainId" => json!(state.chain_id),
+            "eth_blockNumber" => json!(state.block_number),
+            "eth_gasPrice" => json!(state.gas_price),
+            "eth_maxPriorityFeePerGas" => json!(state.max_priority_fee_per_gas),
+            "eth_estimateGas" | "zks_estimateGasL1ToL2" => json!(state.gas_estimate),
+            "eth_getBalance" => json!(state.balance(param(method, &params, 0)?)),
+            "eth_getTransactionCount" => json!(state.nonce(param(method, &params, 0)?)),
+            "eth_getBlockByNumber" => {
+                let number = param::<U64>(method, &params, 0)
+                    .map(|number| number.min(state.block_number))
+                    .unwrap_or(state.block_number);
+                json!(state.block(number))
+            }
+            "eth_getBlockByHash" => {
+                let hash: H256 = param(method, &params, 0)?;
+                json!(state.block(hash.to_low_u64_be().into()))
+            }
+            "eth_feeHistory" => json!(state.fee_history(param(method, &params, 0)?)),
+            "eth_call" => json!(state.call(&param(method, &params, 0)?)?),
+            "eth_sendRawTransaction" => {
+                json!(state.send_raw_transaction(param(method, &params, 0)?)?)
+            }
+            "eth_getTransactionByHash" => {
+                json!(state.transactions.get(&param(method, &params, 0)?))
+            }
+            "eth_getTransactionReceipt" => json!(state.receipts.get(&param(method, &params, 0)?)),
+            "zks_estimateFee" => json!(state.fee()),
+            "zks_getAllAccountBalances" => {
+                let address = param(method, &params, 0)?;
+                json!(HashMap::from([(ETHER_L1_ADDRESS, state.balance(address))]))
+            }
+            "zks_getTokenPrice" => {
+                let token: Address = param(method, &params, 0)?;
+                json!(state
+                    .token_prices
+                    .get(&token)
+                    .ok_or(MockNodeError::CustomError(format!(
+                        "no price set for token {token:?}"
+                    )))?)
+            }
+            "zks_getMainContract" => json!(state.main_contract),
+            "zks_getBridgeContracts" => json!(state.bridge_contracts),
+            "zks_getL2ToL1LogProof" => json!(state.proofs.get(&param(method, &params, 0)?)),
+            "zks_L1BatchNumber" => json!(state.l1_batch_number),
+            "zks_L1ChainId" => json!(state.l1_chain_id),
+            "debug_traceTransaction" => {
+                json!(state.traces.get(&param(method, &params, 0)?).ok_or(
+                    MockNodeError::CustomError("transaction not found".to_owned())
+                )?)
+            }
+            "debug_traceCall" => {
+                let request: Value = param(method, &params, 0)?;
+                let to: Address =
+                    serde_json::from_value(request.get("to").cloned().unwrap_or_default())?;
+                json!(state
+                    .call_traces
+                    .get(&to)
+                    .ok_or(MockNodeError::CustomError(format!(
+                        "no trace scripted for calls to {to:?}"
+                    )))?)
+            }
+            "debug_traceBlockByNumber" | "debug_traceBlockByHash" => {
+                let number = if method == "debug_traceBlockByHash" {
+                    param::<H256>(method, &params, 0)?.to_low_u64_be().into()
+                } else {
+                    param::<U64>(method, &params, 0)?
+                };
+                json!(state
+                    .block_traces
+                    .get(&number)
+                    .into_iter()
+                    .flatten()
+                    .map(|trace| json!({ "result": trace }))
+                    .collect::<Vec<Value>>())
+            }
+            _ => return Err(MockNodeError::UnsupportedMethod(method.to_owned())),
+        };
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for MockZKSNode {
+    type Error = MockNodeError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let response = self.handle(method, serde_json::to_value(params)?)?;
+        Ok(serde_json::from_value(response)?)
+    }
+}
+
+fn param<P>(method: &str, params: &Value, index: usize) -> Result<P, MockNodeError>
+where
+    P: DeserializeOwned,
+{
+    serde_json::from_value(params.get(index).cloned().unwrap_or_default()).map_err(|e| {
+        MockNodeError::InvalidParams {
+            method: method.to_owned(),
+            reason: e.to_string(),
+        }
+    })
+}
+
+fn block_hash(number: U64) -> H256 {
+    H256::from_low_u64_be(number.as_u64())
+}
+
+fn decode_raw_transaction(raw: &[u8]) -> Result<SentTransaction, MockNodeError> {
+    match raw.first() {
+        Some(&EIP712_TX_TYPE) => {
+            let request = Eip712TransactionRequest::decode(raw)
+                .map_err(|e| MockNodeError::CustomError(e.to_string()))?;
+            Ok(SentTransaction {
+                hash: request
+                    .tx_hash()
+                    .map_err(|e| MockNodeError::CustomError(e.to_string()))?,
+                tx_type: EIP712_TX_TYPE,
+                from: request.from,
+                to: Some(request.to),
+                nonce: request.nonce,
+                value: request.value,
+                gas: request.gas_limit.unwrap_or_default(),
+                max_fee_per_gas: request.max_fee_per_gas.unwrap_or_default(),
+                max_priority_fee_per_gas: request.max_priority_fee_per_gas,
+                data: request.data,
+                raw: raw.to_vec().into(),
+            })
+        }
+        _ => {
+            let (transaction, signature) = TypedTransaction::decode_signed(&Rlp::new(raw))
+                .map_err(|e| MockNodeError::CustomError(e.to_string()))?;
+            let (tx_type, max_fee_per_gas, max_priority_fee_per_gas) = match &transaction {
+                TypedTransaction::Eip1559(request) => (
+                    EIP1559_TX_TYPE,
+                    request.max_fee_per_gas.unwrap_or_default(),
+                    request.max_priority_fee_per_gas.unwrap_or_default(),
+                ),
+                TypedTransaction::Eip2930(request) => (
+                    EIP2930_TX_TYPE,
+                    request.tx.gas_price.unwrap_or_default(),
+                    U256::zero(),
+                ),
+                TypedTransaction::Legacy(request) => (
+                    LEGACY_TX_TYPE,
+                    request.gas_price.unwrap_or_default(),
+                    U256::zero(),
+                ),
+            };
+            Ok(SentTransaction {
+                hash: H256(keccak256(raw)),
+                tx_type,
+                from: signature
+                    .recover(transaction.sighash())
+                    .map_err(|e| MockNodeError::CustomError(e.to_string()))?,
+                to: transaction.to().and_then(|to| to.as_address()).copied(),
+                nonce: transaction.nonce().copied().unwrap_or_default(),
+                value: transaction.value().copied().unwrap_or_default(),
+                gas: transaction.gas().copied().unwrap_or_default(),
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                data: transaction.data().cloned().unwrap_or_default(),
+                raw: raw.to_vec().into(),
+            })
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use ethers::{
+    providers::ProviderError,
+    types::{
+        transaction::{eip2718::TypedTransactionError, eip712::Eip712Error},
+        Address, SignatureError,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum RemoteSignerError {
+    #[error("Provider error: {0}")]
+    ProviderError(#[from] ProviderError),
+    #[error("EIP712 error: {0}")]
+    Eip712Error(#[from] Eip712Error),
+    #[error("Signature error: {0}")]
+    SignatureError(#[from] SignatureError),
+    #[error("Serialization error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("Invalid signed transaction: {0}")]
+    TypedTransactionError(#[from] TypedTransactionError),
+    #[error("Signature recovers to {recovered:?} instead of {expected:?}")]
+    UnexpectedSigner {
+        expected: Address,
+        recovered: Address,
+    },
+    #[error("Typed data must be signed with sign_transaction_eip712 or sign_typed_data_json")]
+    UnsupportedTypedData,
+}
@@ -0,0 +1,5 @@
+mod errors;
+pub use errors::RemoteSignerError;
+
+mod signer;
+pub use signer::RemoteSigner;
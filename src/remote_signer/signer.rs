@@ -0,0 +1,170 @@
+use super::RemoteSignerError;
+use crate::eip712::{Eip712Transaction, Eip712TransactionSigner};
+use async_trait::async_trait;
+use ethers::{
+    providers::{Http, JsonRpcClient, Provider},
+    signers::Signer,
+    types::{
+        transaction::{
+            eip2718::TypedTransaction,
+            eip712::{Eip712, TypedData},
+        },
+        Address, Bytes, Signature, H256,
+    },
+    utils::{hash_message, rlp::Rlp},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A [`Signer`] that forwards signing requests to an external JSON-RPC signing service, like
+/// Clef or Web3Signer, using `eth_sign`, `eth_signTransaction` and `eth_signTypedData_v4`.
+///
+/// The service is never trusted: every signature it returns is checked to recover to the
+/// address of the signer.
+#[derive(Clone, Debug)]
+pub struct RemoteSigner<P = Http> {
+    provider: Provider<P>,
+    address: Address,
+    chain_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum SignTransactionResponse {
+    Raw(Bytes),
+    /// Returned by Clef and Geth.
+    Object {
+        raw: Bytes,
+    },
+}
+
+impl<P> RemoteSigner<P>
+where
+    P: JsonRpcClient,
+{
+    /// Creates a signer for the account `address` managed by the service behind `provider`.
+    pub fn new(provider: Provider<P>, address: Address) -> Self {
+        Self {
+            provider,
+            address,
+            chain_id: 1,
+        }
+    }
+
+    /// Signs arbitrary EIP-712 typed data with `eth_signTypedData_v4`.
+    pub async fn sign_typed_data_json(
+        &self,
+        typed_data: &TypedData,
+    ) -> Result<Signature, RemoteSignerError> {
+        let digest = typed_data.encode_eip712()?;
+        let signature: Bytes = self
+            .provider
+            .request("eth_signTypedData_v4", (self.address, typed_data))
+            .await?;
+        self.verify(
+            normalize_v(Signature::try_from(signature.as_ref())?),
+            H256(digest),
+        )
+    }
+
+    fn verify(&self, signature: Signature, hash: H256) -> Result<Signature, RemoteSignerError> {
+        let recovered = signature.recover(hash)?;
+        if recovered != self.address {
+            return Err(RemoteSignerError::UnexpectedSigner {
+                expected: self.address,
+                recovered,
+            });
+        }
+        Ok(signature)
+    }
+}
+
+#[async_trait]
+impl<P> Signer for RemoteSigner<P>
+where
+    P: JsonRpcClient + 'static,
+{
+    type Error = RemoteSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        let message = message.as_ref();
+        let signature: Bytes = self
+            .provider
+            .request("eth_sign", (self.address, Bytes::from(message.to_vec())))
+            .await?;
+        self.verify(
+            normalize_v(Signature::try_from(signature.as_ref())?),
+            hash_message(message),
+        )
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let mut tx = tx.clone();
+        tx.set_from(self.address);
+        if tx.chain_id().is_none() {
+            tx.set_chain_id(self.chain_id);
+        }
+
+        // ethers does not serialize the chain id of typed transactions, signing services need it.
+        let mut request = serde_json::to_value(&tx)?;
+        request["chainId"] = json!(tx.chain_id());
+        let response: SignTransactionResponse = self
+            .provider
+            .request("eth_signTransaction", [request])
+            .await?;
+        let raw = match response {
+            SignTransactionResponse::Raw(raw) | SignTransactionResponse::Object { raw } => raw,
+        };
+        let (_, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw))?;
+        // Recovering from our own sighash also catches services that altered the transaction.
+        self.verify(signature, tx.sighash())
+    }
+
+    /// `eth_signTypedData_v4` needs the typed data and not only its digest, which a generic
+    /// [`Eip712`] payload does not give access to. Use
+    /// [`Eip712TransactionSigner::sign_transaction_eip712`] or
+    /// [`RemoteSigner::sign_typed_data_json`] instead.
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        _payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        Err(RemoteSignerError::UnsupportedTypedData)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+#[async_trait]
+impl<P> Eip712TransactionSigner for RemoteSigner<P>
+where
+    P: JsonRpcClient + 'static,
+{
+    async fn sign_transaction_eip712(
+        &self,
+        transaction: &Eip712Transaction,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_typed_data_json(&transaction.typed_data()?).await
+    }
+}
+
+/// Some services return the recovery id (0 or 1) as `v`, zkSync expects 27 or 28.
+fn normalize_v(mut signature: Signature) -> Signature {
+    if signature.v < 27 {
+        signature.v += 27;
+    }
+    signature
+}
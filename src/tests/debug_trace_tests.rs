@@ -0,0 +1,336 @@
+mod trace_decoder_tests {
+    use crate::abi;
+    use crate::debug_trace::{DecodedParam, DecodedRevert, TraceDecoder};
+    use crate::tests::utils::*;
+    use crate::zks_provider::types::{CallFrame, CallType};
+    use crate::zks_provider::ZKSProvider;
+    use crate::zks_utils::{CONTRACTS_L1_MESSENGER_ADDR, CONTRACTS_L2_ETH_TOKEN_ADDR};
+    use ethers::abi::{encode, parse_abi, Token};
+    use ethers::types::{Address, Bytes, H256, U256};
+    use ethers::utils::id;
+
+    fn call_frame(to: Address, input: Vec<u8>, output: Vec<u8>) -> CallFrame {
+        CallFrame {
+            call_type: CallType::Call,
+            from: Address::from_low_u64_be(1),
+            to: Some(to),
+            value: U256::zero(),
+            gas: 1_000_u64.into(),
+            gas_used: 100_u64.into(),
+            input: input.into(),
+            output: output.into(),
+            error: None,
+            revert_reason: None,
+            calls: vec![],
+        }
+    }
+
+    fn calldata(signature: &str, tokens: &[Token]) -> Vec<u8> {
+        [id(signature).to_vec(), encode(tokens)].concat()
+    }
+
+    #[test]
+    fn test_embedded_abis_load() {
+        assert!(abi::contract_deployer_contract().is_ok());
+        assert!(abi::l2_eth_token_contract().is_ok());
+        assert!(abi::l1_messenger_contract().is_ok());
+        assert!(abi::l2_bridge_contract().is_ok());
+        assert!(abi::paymaster_flow_contract().is_ok());
+    }
+
+    #[test]
+    fn test_decode_system_contract_call() {
+        let receiver = Address::from_low_u64_be(2);
+        let message = b"hello".to_vec();
+        let mut trace = call_frame(
+            CONTRACTS_L2_ETH_TOKEN_ADDR.parse().unwrap(),
+            calldata("withdraw(address)", &[Token::Address(receiver)]),
+            vec![],
+        );
+        trace.value = 10_u64.into();
+        trace.calls.push(call_frame(
+            CONTRACTS_L1_MESSENGER_ADDR.parse().unwrap(),
+            calldata("sendToL1(bytes)", &[Token::Bytes(message.clone())]),
+            encode(&[Token::FixedBytes(H256::repeat_byte(1).as_bytes().to_vec())]),
+        ));
+
+        let decoded = TraceDecoder::new().decode(&trace);
+        let function = decoded.function.unwrap();
+        let sub_call = decoded.calls.first().unwrap();
+        let sub_function = sub_call.function.as_ref().unwrap();
+
+        assert_eq!(decoded.contract.as_deref(), Some("L2EthToken"));
+        assert_eq!(function.signature, "withdraw(address)");
+        assert_eq!(function.inputs[0].name, "_l1Receiver");
+        assert_eq!(function.inputs[0].value, Token::Address(receiver));
+        assert_eq!(sub_call.contract.as_deref(), Some("L1Messenger"));
+        assert_eq!(sub_function.inputs[0].value, Token::Bytes(message));
+        assert_eq!(
+            sub_function.outputs.as_ref().unwrap()[0].value,
+            Token::FixedBytes(H256::repeat_byte(1).as_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_decode_registered_abi_and_reverts() {
+        let token_address = Address::from_low_u64_be(0x1234);
+        let abi = parse_abi(&[
+            "function transfer(address to, uint256 amount) external returns (bool)",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+        ])
+        .unwrap();
+        let decoder = TraceDecoder::new().register_abi(token_address, "Token", abi);
+        let input = calldata(
+            "transfer(address,uint256)",
+            &[
+                Token::Address(Address::from_low_u64_be(2)),
+                Token::Uint(5_u64.into()),
+            ],
+        );
+
+        let mut custom_error = call_frame(
+            token_address,
+            input.clone(),
+            calldata(
+                "InsufficientBalance(uint256,uint256)",
+                &[Token::Uint(1_u64.into()), Token::Uint(5_u64.into())],
+            ),
+        );
+        custom_error.error = Some("execution reverted".to_owned());
+        let mut error_string = call_frame(
+            token_address,
+            input.clone(),
+            calldata("Error(string)", &[Token::String("not allowed".to_owned())]),
+        );
+        error_string.error = Some("execution reverted".to_owned());
+        let mut panic = call_frame(
+            token_address,
+            input.clone(),
+            calldata("Panic(uint256)", &[Token::Uint(0x11_u64.into())]),
+        );
+        panic.error = Some("execution reverted".to_owned());
+        let mut raw = call_frame(token_address, input, vec![0xde, 0xad, 0xbe, 0xef]);
+        raw.error = Some("execution reverted".to_owned());
+
+        let decoded_custom_error = decoder.decode(&custom_error);
+
+        assert!(decoded_custom_error.function.unwrap().outputs.is_none());
+        assert_eq!(
+            decoded_custom_error.revert,
+            Some(DecodedRevert::Custom {
+                name: "InsufficientBalance".to_owned(),
+                params: vec![
+                    DecodedParam {
+                        name: "available".to_owned(),
+                        value: Token::Uint(1_u64.into()),
+                    },
+                    DecodedParam {
+                        name: "required".to_owned(),
+                        value: Token::Uint(5_u64.into()),
+                    },
+                ],
+            })
+        );
+        assert_eq!(
+            decoder.decode(&error_string).revert,
+            Some(DecodedRevert::Error("not allowed".to_owned()))
+        );
+        assert_eq!(
+            decoder.decode(&panic).revert,
+            Some(DecodedRevert::Panic(0x11_u64.into()))
+        );
+        assert_eq!(
+            decoder.decode(&raw).revert,
+            Some(DecodedRevert::Raw(Bytes::from(vec![
+                0xde, 0xad, 0xbe, 0xef
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_render_trace() {
+        let token_address = Address::from_low_u64_be(0x1234);
+        let abi =
+            parse_abi(&["function balanceOf(address owner) external returns (uint256)"]).unwrap();
+        let decoder = TraceDecoder::new().register_abi(token_address, "Token", abi);
+        let mut trace = call_frame(
+            token_address,
+            calldata(
+                "balanceOf(address)",
+                &[Token::Address(Address::from_low_u64_be(2))],
+            ),
+            encode(&[Token::Uint(42_u64.into())]),
+        );
+        let mut unknown = call_frame(Address::from_low_u64_be(3), vec![0xab, 0xcd], vec![]);
+        unknown.call_type = CallType::StaticCall;
+        unknown.revert_reason = Some("unknown failure".to_owned());
+        trace.calls.push(unknown);
+
+        let rendered = decoder.render(&trace);
+
+        assert_eq!(
+            rendered,
+            "CALL Token(0x0000000000000000000000000000000000001234)::balanceOf(owner: 0x0000000000000000000000000000000000000002) -> (42) [gas used: 100]\n  STATICCALL 0x0000000000000000000000000000000000000003::0xabcd reverted: Error(\"unknown failure\") [gas used: 100]\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_traced_transaction() {
+        let era_node = mock_era_node();
+        let tx_hash = H256::repeat_byte(0xaa);
+        era_node.set_transaction_trace(
+            tx_hash,
+            call_frame(
+                CONTRACTS_L2_ETH_TOKEN_ADDR.parse().unwrap(),
+                calldata("withdraw(address)", &[Token::Address(Address::zero())]),
+                vec![],
+            ),
+        );
+
+        let trace = ZKSProvider::debug_trace_transaction(&era_node.provider(), tx_hash, None)
+            .await
+            .unwrap();
+
+        assert!(TraceDecoder::new()
+            .render(&trace)
+            .starts_with("CALL L2EthToken(0x000000000000000000000000000000000000800a)::withdraw(_l1Receiver: 0x0000000000000000000000000000000000000000)"));
+    }
+}
+
+mod gas_profiler_tests {
+    use crate::debug_trace::{GasProfiler, GasUsage, TraceDecoder};
+    use crate::tests::utils::*;
+    use crate::zks_provider::types::{CallFrame, CallType};
+    use crate::zks_provider::ZKSProvider;
+    use crate::zks_utils::CONTRACTS_L2_ETH_TOKEN_ADDR;
+    use ethers::abi::{encode, parse_abi, Token};
+    use ethers::types::{Address, Bytes, U256};
+    use ethers::utils::id;
+
+    fn call_frame(to: Address, input: Vec<u8>, gas_used: u64, calls: Vec<CallFrame>) -> CallFrame {
+        CallFrame {
+            call_type: CallType::Call,
+            from: Address::zero(),
+            to: Some(to),
+            value: U256::zero(),
+            gas: 100_000_u64.into(),
+            gas_used: gas_used.into(),
+            input: input.into(),
+            output: Bytes::default(),
+            error: None,
+            revert_reason: None,
+            calls,
+        }
+    }
+
+    fn transfer_from_to() -> Vec<u8> {
+        let params = [
+            Token::Address(Address::zero()),
+            Token::Address(Address::zero()),
+            Token::Uint(1_u64.into()),
+        ];
+        [
+            id("transferFromTo(address,address,uint256)").to_vec(),
+            encode(&params),
+        ]
+        .concat()
+    }
+
+    fn sample_trace() -> CallFrame {
+        let eth_token: Address = CONTRACTS_L2_ETH_TOKEN_ADDR.parse().unwrap();
+        let contract = Address::from_low_u64_be(0x1234);
+        call_frame(
+            contract,
+            vec![0xaa, 0xbb, 0xcc, 0xdd],
+            1_000,
+            vec![
+                call_frame(eth_token, transfer_from_to(), 300, vec![]),
+                call_frame(
+                    contract,
+                    vec![0x11, 0x22, 0x33, 0x44],
+                    500,
+                    vec![call_frame(eth_token, transfer_from_to(), 200, vec![])],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_gas_profile() {
+        let profile = GasProfiler::new().profile(&sample_trace());
+        let contract = profile.contracts.first().unwrap();
+        let eth_token = profile.contracts.get(1).unwrap();
+        let transfer = profile
+            .functions
+            .iter()
+            .find(|function| function.contract == eth_token.address)
+            .unwrap();
+
+        assert_eq!(profile.total_gas_used, 1_000_u64.into());
+        assert_eq!(contract.address, Some(Address::from_low_u64_be(0x1234)));
+        assert_eq!(
+            contract.gas,
+            GasUsage {
+                calls: 2,
+                inclusive: 1_500_u64.into(),
+                exclusive: 500_u64.into(),
+            }
+        );
+        assert_eq!(eth_token.name.as_deref(), Some("L2EthToken"));
+        assert_eq!(
+            eth_token.gas,
+            GasUsage {
+                calls: 2,
+                inclusive: 500_u64.into(),
+                exclusive: 500_u64.into(),
+            }
+        );
+        assert_eq!(
+            transfer.function.as_deref(),
+            Some("transferFromTo(address,address,uint256)")
+        );
+        assert_eq!(profile.functions.len(), 3);
+    }
+
+    #[test]
+    fn test_gas_profile_exports() {
+        let profile = GasProfiler::new().profile(&sample_trace());
+        let json: serde_json::Value = serde_json::from_str(&profile.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["totalGasUsed"], "0x3e8");
+        assert_eq!(json["contracts"][0]["calls"], 2_u64);
+        assert_eq!(json["functions"][0]["selector"], "0xaabbccdd");
+        assert_eq!(
+            profile.to_folded_stacks(),
+            "0x0000000000000000000000000000000000001234::0xaabbccdd 200\n\
+             0x0000000000000000000000000000000000001234::0xaabbccdd;0x0000000000000000000000000000000000001234::0x11223344 300\n\
+             0x0000000000000000000000000000000000001234::0xaabbccdd;0x0000000000000000000000000000000000001234::0x11223344;L2EthToken::transferFromTo 200\n\
+             0x0000000000000000000000000000000000001234::0xaabbccdd;L2EthToken::transferFromTo 300\n"
+        );
+    }
+
+    #[test]
+    fn test_gas_profile_folded_stack_names() {
+        let contract = Address::from_low_u64_be(0x1234);
+        let abi = parse_abi(&["function ping()"]).unwrap();
+        let decoder = TraceDecoder::new().register_abi(contract, "My Token;v2", abi);
+        let trace = call_frame(contract, id("ping()").to_vec(), 100, vec![]);
+        let profile = GasProfiler::new().decoder(decoder).profile(&trace);
+
+        assert_eq!(profile.to_folded_stacks(), "My_Token_v2::ping 100\n");
+    }
+
+    #[tokio::test]
+    async fn test_gas_profile_block() {
+        let era_node = mock_era_node();
+        era_node.set_block_traces(1_u64, vec![sample_trace(), sample_trace()]);
+
+        let traces = ZKSProvider::debug_trace_block_by_number(&era_node.provider(), 1_u64, None)
+            .await
+            .unwrap();
+        let profile = GasProfiler::new().profile_traces(&traces);
+
+        assert_eq!(profile.total_gas_used, 2_000_u64.into());
+        assert_eq!(profile.contracts.first().unwrap().gas.calls, 4);
+    }
+}
@@ -0,0 +1,189 @@
+mod eip712_decoding_tests {
+    use crate::eip712::{Eip712Meta, Eip712Transaction, Eip712TransactionRequest, PaymasterParams};
+    use crate::tests::utils::*;
+    use crate::zks_utils::{EIP712_TX_TYPE, ERA_CHAIN_ID};
+    use crate::zks_wallet::{TransferRequest, ZKSWallet};
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::transaction::eip712::Eip712;
+    use ethers::types::{Address, Bytes, H256, U256};
+    use ethers::utils::keccak256;
+    use std::str::FromStr;
+
+    fn wallet() -> LocalWallet {
+        LocalWallet::from_str("0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959")
+            .unwrap()
+            .with_chain_id(ERA_CHAIN_ID)
+    }
+
+    fn request(from: Address) -> Eip712TransactionRequest {
+        Eip712TransactionRequest::new()
+            .from(from)
+            .to(Address::from_low_u64_be(0x1234))
+            .nonce(3_u64)
+            .value(1_000_u64)
+            .data(vec![0xde, 0xad, 0xbe, 0xef])
+            .max_priority_fee_per_gas(1_u64)
+            .max_fee_per_gas(250_000_000_u64)
+            .gas_limit(1_000_000_u64)
+            .custom_data(Eip712Meta::new().factory_deps(vec![vec![0_u8; 32]]))
+    }
+
+    #[tokio::test]
+    async fn test_decode_signed_transaction() {
+        let wallet = wallet();
+        let request = request(wallet.address());
+        let signable_data: Eip712Transaction = request.clone().try_into().unwrap();
+        let signature = wallet.sign_typed_data(&signable_data).await.unwrap();
+        let raw: Bytes = [&[EIP712_TX_TYPE], &*request.rlp_signed(signature).unwrap()]
+            .concat()
+            .into();
+
+        let (decoded, decoded_signature) = Eip712TransactionRequest::decode_signed(&raw).unwrap();
+
+        assert_eq!(decoded_signature, signature);
+        assert_eq!(decoded.from, request.from);
+        assert_eq!(decoded.to, request.to);
+        assert_eq!(decoded.nonce, request.nonce);
+        assert_eq!(decoded.value, request.value);
+        assert_eq!(decoded.data, request.data);
+        assert_eq!(decoded.chain_id, U256::from(ERA_CHAIN_ID));
+        assert_eq!(decoded.max_fee_per_gas, request.max_fee_per_gas);
+        assert_eq!(decoded.gas_limit, request.gas_limit);
+        assert_eq!(
+            decoded.custom_data.gas_per_pubdata,
+            request.custom_data.gas_per_pubdata
+        );
+        assert_eq!(
+            decoded.custom_data.factory_deps,
+            request.custom_data.factory_deps
+        );
+        assert_eq!(
+            decoded.custom_data.custom_signature,
+            Some(signature.to_vec().into())
+        );
+        assert!(decoded.custom_data.paymaster_params.is_none());
+        assert_eq!(decoded.recover_signer().unwrap(), wallet.address());
+        assert_eq!(
+            Eip712TransactionRequest::decode(raw.get(1..).unwrap())
+                .unwrap()
+                .nonce,
+            request.nonce
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_signer_of_sent_transaction() {
+        let era_node = mock_era_node();
+        let wallet = wallet();
+        era_node.set_balance(wallet.address(), U256::exp10(18));
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+
+        let request = TransferRequest::new(1_u64.into())
+            .to(Address::from_low_u64_be(0x1234))
+            .from(zk_wallet.l2_address());
+        zk_wallet.transfer_eip712(&request).await.unwrap();
+        let sent_transaction = era_node.sent_transactions().pop().unwrap();
+        let decoded = Eip712TransactionRequest::decode(&sent_transaction.raw).unwrap();
+
+        assert_eq!(decoded.recover_signer().unwrap(), zk_wallet.l2_address());
+    }
+
+    #[tokio::test]
+    async fn test_local_transaction_hash() {
+        let wallet = wallet();
+        let request = request(wallet.address());
+        let signable_data: Eip712Transaction = request.clone().try_into().unwrap();
+        let signature = wallet.sign_typed_data(&signable_data).await.unwrap();
+        let signed_request = request.clone().custom_data(
+            request
+                .custom_data
+                .clone()
+                .custom_signature(signature.to_vec()),
+        );
+
+        let digest = request.signing_digest().unwrap();
+        let expected_hash = H256(keccak256(
+            [digest.as_bytes(), &keccak256(signature.to_vec())].concat(),
+        ));
+
+        assert_eq!(digest, H256(signable_data.encode_eip712().unwrap()));
+        assert_eq!(signature.recover(digest).unwrap(), wallet.address());
+        assert_eq!(signed_request.tx_hash().unwrap(), expected_hash);
+        assert_eq!(
+            signable_data.tx_hash(&signature.to_vec()).unwrap(),
+            expected_hash
+        );
+        assert!(request.tx_hash().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_local_hash_of_sent_transaction() {
+        let era_node = mock_era_node();
+        let wallet = wallet();
+        era_node.set_balance(wallet.address(), U256::exp10(18));
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+
+        let request = TransferRequest::new(1_u64.into())
+            .to(Address::from_low_u64_be(0x1234))
+            .from(zk_wallet.l2_address());
+        let tx_hash = zk_wallet.transfer_eip712(&request).await.unwrap();
+        let sent_transaction = era_node.sent_transactions().pop().unwrap();
+        let decoded = Eip712TransactionRequest::decode(&sent_transaction.raw).unwrap();
+
+        assert_eq!(decoded.tx_hash().unwrap(), tx_hash);
+        assert_eq!(sent_transaction.hash, tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_decode_transaction_with_paymaster() {
+        let wallet = wallet();
+        let paymaster_params =
+            PaymasterParams::general(Address::from_low_u64_be(0xfee), vec![1_u8, 2].into())
+                .unwrap();
+        let request = request(wallet.address()).custom_data(
+            Eip712Meta::new()
+                .factory_deps(vec![vec![0_u8; 32]])
+                .paymaster_params(paymaster_params.clone()),
+        );
+        let signable_data: Eip712Transaction = request.clone().try_into().unwrap();
+        let signature = wallet.sign_typed_data(&signable_data).await.unwrap();
+        let raw: Bytes = [&[EIP712_TX_TYPE], &*request.rlp_signed(signature).unwrap()]
+            .concat()
+            .into();
+        let decoded = Eip712TransactionRequest::decode(&raw).unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(decoded.recover_signer().unwrap(), wallet.address());
+        assert_eq!(
+            decoded
+                .custom_data
+                .paymaster_params
+                .unwrap()
+                .paymaster_input,
+            paymaster_params.paymaster_input
+        );
+        assert!(json["eip712Meta"]["paymasterParams"]["paymasterInput"].is_array());
+        assert_eq!(
+            serde_json::from_value::<Eip712TransactionRequest>(json)
+                .unwrap()
+                .custom_data
+                .paymaster_params
+                .unwrap()
+                .paymaster_input,
+            paymaster_params.paymaster_input
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_transaction() {
+        assert!(Eip712TransactionRequest::decode(&[EIP712_TX_TYPE, 0xc0]).is_err());
+        assert!(Eip712TransactionRequest::decode(&[0xff, 0x01]).is_err());
+        assert!(Eip712TransactionRequest::decode_signed(
+            &request(Address::zero())
+                .custom_data(Eip712Meta::new().custom_signature(vec![1_u8; 10]))
+                .rlp_unsigned()
+                .unwrap()
+        )
+        .is_err());
+    }
+}
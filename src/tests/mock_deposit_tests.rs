@@ -0,0 +1,460 @@
+mod mock_deposit_tests {
+    use crate::contracts::main_contract::{decode_new_priority_request, L2CanonicalTransaction};
+
+    use crate::tests::utils::*;
+    use crate::zks_provider::types::{BridgeContracts, L2ToL1Log, Proof, ZksTransactionReceipt};
+
+    use crate::zks_utils::{
+        apply_l1_to_l2_alias, BOOTLOADER_ADDRESS, DEFAULT_ERC20_DEPOSIT_GAS_LIMIT,
+        DEFAULT_ERC20_DEPOSIT_L2_GAS_LIMIT, DEPOSIT_GAS_PER_PUBDATA_LIMIT,
+    };
+    use crate::zks_wallet::{DepositQuote, DepositRequest, ExecuteRequest, ZKSWalletError};
+    use ethers::abi::{encode, Token, Tokenizable};
+    use ethers::providers::Middleware;
+
+    use ethers::types::{Address, Bytes, Eip1559TransactionRequest, Log, Transaction, H256, U256};
+    use ethers::utils::id;
+
+    use std::{env, fs, time::Duration};
+
+    #[tokio::test]
+    async fn test_mock_deposit() {
+        let main_contract: Address = MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap();
+        let base_cost = U256::from(1_000_000_u64);
+        let l2_tx_hash = H256::repeat_byte(0x22);
+        let (era_node, eth_node, zk_wallet) =
+            mock_l1_to_l2_setup(base_cost, main_contract, l2_tx_hash);
+        eth_node.set_gas_estimate(150_000_u64);
+        era_node.set_gas_estimate(400_000_u64);
+        let amount = ether("0.01");
+        let request = DepositRequest::new(amount);
+        let quote = zk_wallet.quote_deposit(&request).await.unwrap();
+
+        assert!(eth_node.sent_transactions().is_empty());
+        assert!(quote.approval.is_none());
+        assert_eq!(quote.base_cost, base_cost);
+        assert_eq!(quote.gas_limit, 150_000_u64.into());
+        assert_eq!(quote.l2_gas_limit, 400_000_u64.into());
+        assert_eq!(quote.l1_value(), amount + base_cost);
+
+        let deposit = zk_wallet
+            .deposit(&request)
+            .await
+            .unwrap()
+            .polling_interval(Duration::from_millis(10));
+
+        let receipt = zk_wallet
+            .get_eth_provider()
+            .unwrap()
+            .get_transaction_receipt(deposit.l1_tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let fee = receipt.effective_gas_price.unwrap() * receipt.gas_used.unwrap();
+        let sent_transaction = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(receipt.status.unwrap(), 1_u8.into());
+        assert_eq!(sent_transaction.to, Some(main_contract));
+        assert_eq!(
+            sent_transaction.data.get(..4).unwrap(),
+            id("requestL2Transaction(address,uint256,bytes,uint256,uint256,bytes[],address)")
+        );
+        assert_eq!(sent_transaction.value, amount + base_cost);
+        assert_eq!(sent_transaction.gas, 150_000_u64.into());
+        // The estimated L2 gas limit.
+        assert_eq!(
+            sent_transaction.data.get(4 + 3 * 32..4 + 4 * 32).unwrap(),
+            encode(&[Token::Uint(400_000_u64.into())])
+        );
+        assert_eq!(
+            eth_node.balance(zk_wallet.l1_address()),
+            ether("10") - amount - base_cost - fee
+        );
+        assert_eq!(deposit.l2_tx_hash, l2_tx_hash);
+
+        // The deposit is not executed on L2 yet.
+        assert!(deposit
+            .clone()
+            .timeout(Some(Duration::from_millis(50)))
+            .l2_receipt()
+            .await
+            .is_err());
+
+        let mut l2_receipt = ZksTransactionReceipt::default();
+        l2_receipt.receipt.transaction_hash = l2_tx_hash;
+        l2_receipt.receipt.status = Some(1_u64.into());
+        era_node
+            .set_response("eth_getTransactionReceipt", &l2_receipt)
+            .unwrap();
+
+        assert_eq!(
+            deposit.l2_receipt().await.unwrap().transaction_hash,
+            l2_tx_hash
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_deposit_fees() {
+        let main_contract: Address = MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap();
+        let (_era_node, eth_node, zk_wallet) =
+            mock_l1_to_l2_setup(1_000_000_u64.into(), main_contract, H256::repeat_byte(0x22));
+        let request = DepositRequest::new(ether("0.01"))
+            .max_fee_per_gas(Some(90_000_000_000_u64.into()))
+            .max_priority_fee_per_gas(Some(2_000_000_000_u64.into()));
+        zk_wallet.deposit(&request).await.unwrap();
+        let sent_transaction = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(sent_transaction.tx_type, 2);
+        assert_eq!(sent_transaction.max_fee_per_gas, 90_000_000_000_u64.into());
+        assert_eq!(
+            sent_transaction.max_priority_fee_per_gas,
+            2_000_000_000_u64.into()
+        );
+
+        // A gas price only stands in for a missing max fee.
+        let legacy_request = DepositRequest::new(ether("0.01"))
+            .gas_price(Some(50_000_000_000_u64.into()))
+            .max_priority_fee_per_gas(Some(2_000_000_000_u64.into()));
+        let quote = zk_wallet.quote_deposit(&legacy_request).await.unwrap();
+        assert_eq!(quote.max_fee_per_gas, 50_000_000_000_u64.into());
+        let quote = zk_wallet
+            .quote_deposit(&legacy_request.max_fee_per_gas(Some(90_000_000_000_u64.into())))
+            .await
+            .unwrap();
+        assert_eq!(quote.max_fee_per_gas, 90_000_000_000_u64.into());
+
+        // Missing fees come from the L1 fee history.
+        let (max_fee_per_gas, max_priority_fee_per_gas) = zk_wallet
+            .get_eth_provider()
+            .unwrap()
+            .estimate_eip1559_fees(None)
+            .await
+            .unwrap();
+        zk_wallet
+            .deposit(&DepositRequest::new(ether("0.01")))
+            .await
+            .unwrap();
+        let sent_transaction = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(sent_transaction.max_fee_per_gas, max_fee_per_gas);
+        assert_eq!(
+            sent_transaction.max_priority_fee_per_gas,
+            max_priority_fee_per_gas
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_quote_erc20_deposit() {
+        let l1_bridge = Address::from_low_u64_be(0xa1);
+        let l1_token = Address::from_low_u64_be(0x70c);
+        let base_cost = U256::from(1_000_000_u64);
+        let (era_node, eth_node, zk_wallet) =
+            mock_l1_to_l2_setup(base_cost, l1_bridge, H256::repeat_byte(0x22));
+        eth_node.set_gas_estimate(150_000_u64);
+        eth_node.set_call_response(
+            l1_token,
+            id("allowance(address,address)"),
+            encode(&[Token::Uint(0_u64.into())]),
+        );
+        era_node.set_gas_estimate(400_000_u64);
+        era_node.set_bridge_contracts(BridgeContracts {
+            l1_erc20_default_bridge: l1_bridge,
+            l2_erc20_default_bridge: Address::from_low_u64_be(0xa2),
+        });
+
+        let request = DepositRequest::new(1_000_u64.into()).token(Some(l1_token));
+        let l2_gas_limit = |data: &Bytes| U256::from_big_endian(&data[4 + 3 * 32..4 + 4 * 32]);
+
+        // Reverting metadata calls fail the quote instead of falling back to a default.
+        assert!(zk_wallet.quote_deposit(&request).await.is_err());
+
+        for (function, output) in [
+            ("name()", encode(&[Token::String("Token".to_owned())])),
+            ("symbol()", encode(&[Token::String("TKN".to_owned())])),
+            ("decimals()", encode(&[Token::Uint(18_u64.into())])),
+        ] {
+            eth_node.set_call_response(l1_token, id(function), output);
+        }
+        // The L1 gas can't be estimated before the approval.
+        let quote = zk_wallet.quote_deposit(&request).await.unwrap();
+        let approval = quote.approval.clone().unwrap();
+
+        assert!(eth_node.sent_transactions().is_empty());
+        assert_eq!(quote.base_cost, base_cost);
+        assert_eq!(quote.l2_gas_limit, 400_000_u64.into());
+        assert_eq!(quote.gas_limit, DEFAULT_ERC20_DEPOSIT_GAS_LIMIT.into());
+        assert_eq!(approval.to, Some(l1_token.into()));
+        assert_eq!(
+            approval.data.unwrap().to_vec(),
+            [
+                &id("approve(address,uint256)")[..],
+                &encode(&[Token::Address(l1_bridge), Token::Uint(1_000_u64.into())]),
+            ]
+            .concat()
+        );
+        assert_eq!(
+            quote.max_total_cost(),
+            base_cost + (quote.gas_limit + 150_000_u64) * quote.max_fee_per_gas
+        );
+        assert_eq!(
+            DepositQuote {
+                max_fee_per_gas: U256::MAX,
+                ..quote.clone()
+            }
+            .max_total_cost(),
+            U256::MAX
+        );
+
+        zk_wallet.deposit(&request).await.unwrap();
+        let mut sent_transactions = eth_node.sent_transactions();
+        let deposit = sent_transactions.pop().unwrap();
+
+        assert_eq!(sent_transactions.pop().unwrap().to, Some(l1_token));
+        assert_eq!(deposit.to, Some(l1_bridge));
+        assert_eq!(deposit.gas, 150_000_u64.into());
+        assert_eq!(deposit.value, base_cost);
+        assert_eq!(l2_gas_limit(&deposit.data), 400_000_u64.into());
+
+        eth_node.set_call_response(
+            l1_token,
+            id("allowance(address,address)"),
+            encode(&[Token::Uint(1_000_u64.into())]),
+        );
+        let quote = zk_wallet.quote_deposit(&request).await.unwrap();
+
+        assert!(quote.approval.is_none());
+        assert_eq!(quote.gas_limit, 150_000_u64.into());
+        assert_eq!(quote.l2_gas_limit, 400_000_u64.into());
+
+        let sent_before = eth_node.sent_transactions().len();
+        zk_wallet.deposit(&request).await.unwrap();
+        let sent_transactions = eth_node.sent_transactions();
+
+        assert_eq!(sent_transactions.len(), sent_before + 1);
+        assert_eq!(
+            sent_transactions.last().unwrap().data,
+            quote.transaction.data.unwrap()
+        );
+
+        // The L2 side of a custom bridge is unknown, its L2 gas can't be estimated.
+        let custom_bridge = Address::from_low_u64_be(0xb1);
+        let quote = zk_wallet
+            .quote_deposit(&request.bridge_address(Some(custom_bridge)))
+            .await
+            .unwrap();
+
+        assert_eq!(quote.transaction.to, Some(custom_bridge.into()));
+        assert_eq!(
+            quote.l2_gas_limit,
+            DEFAULT_ERC20_DEPOSIT_L2_GAS_LIMIT.into()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_request_execute() {
+        let main_contract: Address = MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap();
+        let base_cost = U256::from(1_000_000_u64);
+        let l2_gas_limit = U256::from(700_000_u64);
+        let l2_tx_hash = H256::repeat_byte(0x33);
+        let target = Address::from_low_u64_be(0x9a);
+        let refund_recipient = Address::from_low_u64_be(0x4e);
+        let calldata = Bytes::from(id("execute()").to_vec());
+        let factory_dep = Bytes::from_static(&[0_u8; 32]);
+        let (era_node, eth_node, zk_wallet) =
+            mock_l1_to_l2_setup(base_cost, main_contract, l2_tx_hash);
+        era_node.set_gas_estimate(l2_gas_limit);
+
+        let request = ExecuteRequest::new(target, calldata.clone())
+            .l2_value(ether("0.5"))
+            .factory_deps(vec![factory_dep.clone()])
+            .refund_recipient(refund_recipient);
+        let quote = zk_wallet.quote_request_execute(&request).await.unwrap();
+        let handle = zk_wallet.request_execute(&request).await.unwrap();
+        let sent_transaction = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(quote.l2_gas_limit, l2_gas_limit);
+        assert_eq!(quote.base_cost, base_cost);
+        assert_eq!(quote.l1_value(), sent_transaction.value);
+        assert_eq!(quote.transaction.data, Some(sent_transaction.data.clone()));
+
+        assert_eq!(handle.l1_tx_hash, sent_transaction.hash);
+        assert_eq!(handle.l2_tx_hash, l2_tx_hash);
+        assert_eq!(sent_transaction.to, Some(main_contract));
+        assert_eq!(sent_transaction.value, ether("0.5") + base_cost);
+        assert_eq!(
+            sent_transaction.data.to_vec(),
+            [
+                &id("requestL2Transaction(address,uint256,bytes,uint256,uint256,bytes[],address)")
+                    [..],
+                &encode(&[
+                    Token::Address(target),
+                    Token::Uint(ether("0.5")),
+                    Token::Bytes(calldata.to_vec()),
+                    Token::Uint(l2_gas_limit),
+                    Token::Uint(DEPOSIT_GAS_PER_PUBDATA_LIMIT.into()),
+                    Token::Array(vec![Token::Bytes(factory_dep.to_vec())]),
+                    Token::Address(refund_recipient),
+                ]),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn test_decode_new_priority_request() {
+        let transaction = L2CanonicalTransaction {
+            tx_type: 255_u64.into(),
+            from: 1_u64.into(),
+            to: 2_u64.into(),
+            gas_limit: 300_000_u64.into(),
+            gas_per_pubdata_byte_limit: 800_u64.into(),
+            max_fee_per_gas: 250_000_000_u64.into(),
+            nonce: 42_u64.into(),
+            value: ether("1"),
+            data: Bytes::from_static(&[0xab, 0xcd]),
+            ..Default::default()
+        };
+        let l2_tx_hash = H256::repeat_byte(0x22);
+        let mut log = new_priority_request_log(Address::zero(), l2_tx_hash);
+        log.data = encode(&[
+            Token::Uint(42_u64.into()),
+            Token::FixedBytes(l2_tx_hash.as_bytes().to_vec()),
+            Token::Uint(1_000_u64.into()),
+            transaction.clone().into_token(),
+            Token::Array(vec![]),
+        ])
+        .into();
+
+        let request = decode_new_priority_request(&log).unwrap();
+
+        assert_eq!(request.tx_id, 42_u64.into());
+        assert_eq!(H256(request.tx_hash), l2_tx_hash);
+        assert_eq!(request.expiration_timestamp, 1_000_u64);
+        assert_eq!(request.transaction, transaction);
+    }
+
+    /// Replays a `NewPriorityRequest` log recorded from a real L1 deposit receipt, saved as JSON
+    /// at the path in `ZKSYNC_WEB3_RS_RECORDED_PRIORITY_REQUEST_LOG`. Its `txHash` is computed
+    /// by the main contract, so it checks the local hash against the chain without a node.
+    #[test]
+    #[ignore = "needs a NewPriorityRequest log recorded from a real L1 deposit"]
+    fn test_recorded_new_priority_request_hash() {
+        let path = env::var("ZKSYNC_WEB3_RS_RECORDED_PRIORITY_REQUEST_LOG").unwrap();
+        let log: Log = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+
+        let request = decode_new_priority_request(&log).unwrap();
+
+        assert_eq!(request.transaction.hash(), H256(request.tx_hash));
+    }
+
+    #[tokio::test]
+    async fn test_mock_claim_failed_deposit() {
+        let l1_bridge = Address::from_low_u64_be(0xa1);
+        let l1_token = Address::from_low_u64_be(0x70c);
+        let l2_tx_hash = H256::repeat_byte(0x22);
+        // The bridge asks the main contract for the L2 deposit transaction.
+        let (era_node, eth_node, zk_wallet) =
+            mock_l1_to_l2_setup(U256::zero(), l1_bridge, l2_tx_hash);
+        let l1_tx_hash = zk_wallet
+            .get_eth_provider()
+            .unwrap()
+            .send_transaction(Eip1559TransactionRequest::new().to(l1_bridge), None)
+            .await
+            .unwrap()
+            .tx_hash();
+        let mut l2_receipt = ZksTransactionReceipt {
+            l1_batch_number: Some(3_u64.into()),
+            l1_batch_tx_index: Some(1_u64.into()),
+            l2_to_l1_logs: vec![L2ToL1Log {
+                sender: BOOTLOADER_ADDRESS,
+                key: l2_tx_hash,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        l2_receipt.receipt.transaction_hash = l2_tx_hash;
+        l2_receipt.receipt.from = apply_l1_to_l2_alias(l1_bridge);
+        l2_receipt.receipt.status = Some(0_u64.into());
+        era_node
+            .set_response("eth_getTransactionReceipt", &l2_receipt)
+            .unwrap();
+        era_node
+            .set_response(
+                "eth_getTransactionByHash",
+                Transaction {
+                    hash: l2_tx_hash,
+                    from: l2_receipt.receipt.from,
+                    input: [
+                        &id("finalizeDeposit(address,address,address,uint256,bytes)")[..],
+                        &encode(&[
+                            Token::Address(zk_wallet.l1_address()),
+                            Token::Address(zk_wallet.l2_address()),
+                            Token::Address(l1_token),
+                            Token::Uint(1_000_u64.into()),
+                            Token::Bytes(vec![]),
+                        ]),
+                    ]
+                    .concat()
+                    .into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        era_node.set_l2_to_l1_log_proof(
+            l2_tx_hash,
+            Proof {
+                id: 7,
+                merkle_proof: vec![H256::zero()],
+                root: Bytes::default(),
+            },
+        );
+
+        assert_eq!(
+            zk_wallet
+                .l2_hash_of_l1_transaction(l1_tx_hash)
+                .await
+                .unwrap(),
+            l2_tx_hash
+        );
+        zk_wallet.claim_failed_deposit(l1_tx_hash).await.unwrap();
+        let claim = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(claim.to, Some(l1_bridge));
+        assert_eq!(
+            claim.data.to_vec(),
+            [
+                &id("claimFailedDeposit(address,address,bytes32,uint256,uint256,uint16,bytes32[])")
+                    [..],
+                &encode(&[
+                    Token::Address(zk_wallet.l1_address()),
+                    Token::Address(l1_token),
+                    Token::FixedBytes(l2_tx_hash.as_bytes().to_vec()),
+                    Token::Uint(3_u64.into()),
+                    Token::Uint(7_u64.into()),
+                    Token::Uint(1_u64.into()),
+                    Token::Array(vec![Token::FixedBytes(vec![0; 32])]),
+                ]),
+            ]
+            .concat()
+        );
+
+        // The bridge takes the index as a uint16.
+        l2_receipt.l1_batch_tx_index = Some(u64::from(u16::MAX).saturating_add(1).into());
+        era_node
+            .set_response("eth_getTransactionReceipt", &l2_receipt)
+            .unwrap();
+        assert!(matches!(
+            zk_wallet.claim_failed_deposit(l1_tx_hash).await,
+            Err(ZKSWalletError::CustomError(_))
+        ));
+
+        l2_receipt.receipt.status = Some(1_u64.into());
+        era_node
+            .set_response("eth_getTransactionReceipt", &l2_receipt)
+            .unwrap();
+        assert!(matches!(
+            zk_wallet.claim_failed_deposit(l1_tx_hash).await,
+            Err(ZKSWalletError::DepositSucceeded(hash)) if hash == l2_tx_hash
+        ));
+    }
+}
@@ -0,0 +1,260 @@
+mod mock_paymaster_tests {
+
+    use crate::eip712::{Eip712TransactionRequest, PaymasterParams};
+
+    use crate::tests::utils::*;
+    use crate::zks_provider::types::Fee;
+
+    use crate::zks_wallet::{
+        TokenRateOracle, TransferRequest, WithdrawRequest, ZKSWallet, ZKSWalletError,
+    };
+    use ethers::abi::{encode, Token};
+    use ethers::providers::ProviderError;
+    use ethers::signers::Signer;
+
+    use ethers::types::{Address, Bytes, U256};
+    use ethers::utils::id;
+
+    #[tokio::test]
+    async fn test_mock_transfer_and_withdraw_with_paymaster() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let token = Address::from_low_u64_be(0x70c);
+        let receiver_address = Address::from_low_u64_be(0x1234);
+        era_node.set_balance(wallet.address(), ether("10"));
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+        let paymaster_params =
+            PaymasterParams::approval_based(paymaster, token, 1_u64.into(), Bytes::default())
+                .unwrap();
+        zk_wallet
+            .transfer(
+                &TransferRequest::new(ether("1"))
+                    .to(receiver_address)
+                    .from(zk_wallet.l2_address())
+                    .paymaster_params(paymaster_params.clone()),
+            )
+            .await
+            .unwrap();
+        zk_wallet
+            .withdraw(
+                &WithdrawRequest::new(ether("1"))
+                    .to(receiver_address)
+                    .from(zk_wallet.l2_address())
+                    .paymaster_params(
+                        PaymasterParams::general(paymaster, Bytes::default()).unwrap(),
+                    ),
+            )
+            .await
+            .unwrap();
+        let sent_transactions = era_node.sent_transactions();
+        let transfer = Eip712TransactionRequest::decode(&sent_transactions[0].raw).unwrap();
+        let withdrawal = Eip712TransactionRequest::decode(&sent_transactions[1].raw).unwrap();
+        let transfer_paymaster = transfer.custom_data.paymaster_params.clone().unwrap();
+        let withdrawal_paymaster = withdrawal.custom_data.paymaster_params.clone().unwrap();
+
+        assert_eq!(transfer.recover_signer().unwrap(), zk_wallet.l2_address());
+        assert_eq!(transfer_paymaster.paymaster, Some(paymaster));
+        assert_eq!(
+            transfer_paymaster.paymaster_input,
+            paymaster_params.paymaster_input
+        );
+        assert_eq!(
+            transfer_paymaster
+                .paymaster_input
+                .unwrap()
+                .get(..4)
+                .unwrap(),
+            &id("approvalBased(address,uint256,bytes)")
+        );
+        assert_eq!(
+            withdrawal_paymaster
+                .paymaster_input
+                .unwrap()
+                .get(..4)
+                .unwrap(),
+            &id("general(bytes)")
+        );
+        assert_eq!(withdrawal.recover_signer().unwrap(), zk_wallet.l2_address());
+        assert_eq!(era_node.balance(receiver_address), ether("1"));
+    }
+
+    /// Takes twice the fee, in any token.
+    #[derive(Debug)]
+    struct DoubleFeeOracle;
+
+    #[async_trait::async_trait]
+    impl TokenRateOracle for DoubleFeeOracle {
+        async fn fee_in_token(&self, _token: Address, fee: U256) -> Result<U256, ProviderError> {
+            Ok(fee * 2_u64)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_paymaster_allowance_from_fee() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let token = Address::from_low_u64_be(0x70c);
+        let receiver_address = Address::from_low_u64_be(0x1234);
+        let fee = Fee {
+            gas_limit: 1_000_000_u64.into(),
+            gas_per_pubdata_limit: 50_000_u64.into(),
+            max_fee_per_gas: 250_000_000_u64.into(),
+            max_priority_fee_per_gas: 0_u64.into(),
+        };
+        era_node.set_balance(wallet.address(), ether("10"));
+        era_node.set_fee(fee);
+        era_node.set_token_price(Address::zero(), "2000");
+        era_node.set_token_price(token, "0.5");
+        era_node.set_call_response(token, id("decimals()"), encode(&[Token::Uint(6_u8.into())]));
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+        let request = TransferRequest::new(1_u64.into())
+            .to(receiver_address)
+            .from(zk_wallet.l2_address());
+        let eip712_request: Eip712TransactionRequest = request.clone().into();
+        let paymaster_params = zk_wallet
+            .approval_based_paymaster_params(paymaster, token, Bytes::default(), &eip712_request)
+            .await
+            .unwrap();
+        zk_wallet
+            .transfer_eip712(&request.clone().paymaster_params(paymaster_params))
+            .await
+            .unwrap();
+        let zk_wallet = zk_wallet.with_token_rate_oracle(DoubleFeeOracle);
+        let paymaster_params = zk_wallet
+            .approval_based_paymaster_params(paymaster, token, Bytes::default(), &eip712_request)
+            .await
+            .unwrap();
+        zk_wallet
+            .transfer_eip712(&request.paymaster_params(paymaster_params))
+            .await
+            .unwrap();
+        let sent_transactions = era_node.sent_transactions();
+        let paymaster_input = |raw: &Bytes| {
+            let sent = Eip712TransactionRequest::decode(raw).unwrap();
+            assert_eq!(sent.gas_limit, Some(fee.gas_limit));
+            assert_eq!(sent.max_fee_per_gas, Some(fee.max_fee_per_gas));
+            sent.custom_data
+                .paymaster_params
+                .unwrap()
+                .paymaster_input
+                .unwrap()
+        };
+        let max_fee = fee.gas_limit * fee.max_fee_per_gas;
+
+        // 0.00025 ETH at 2000 USD is 0.5 USD, or 1 token with 6 decimals.
+        assert_eq!(
+            paymaster_input(&sent_transactions[0].raw),
+            PaymasterParams::approval_based(
+                paymaster,
+                token,
+                1_000_000_u64.into(),
+                Bytes::default()
+            )
+            .unwrap()
+            .paymaster_input
+            .unwrap()
+        );
+        assert_eq!(
+            paymaster_input(&sent_transactions[1].raw),
+            PaymasterParams::approval_based(paymaster, token, max_fee * 2_u64, Bytes::default())
+                .unwrap()
+                .paymaster_input
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_paymaster_allowance_after_fee_rise() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let token = Address::from_low_u64_be(0x70c);
+        let quoted_fee = Fee {
+            gas_limit: 1_000_000_u64.into(),
+            gas_per_pubdata_limit: 50_000_u64.into(),
+            max_fee_per_gas: 250_000_000_u64.into(),
+            max_priority_fee_per_gas: 0_u64.into(),
+        };
+        let fee = Fee {
+            gas_limit: 1_200_000_u64.into(),
+            max_fee_per_gas: 300_000_000_u64.into(),
+            ..quoted_fee
+        };
+        era_node.set_balance(wallet.address(), ether("10"));
+        era_node.set_fee(quoted_fee);
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None)
+            .unwrap()
+            .with_token_rate_oracle(DoubleFeeOracle);
+        let request: Eip712TransactionRequest = TransferRequest::new(1_u64.into())
+            .to(Address::from_low_u64_be(0x1234))
+            .from(zk_wallet.l2_address())
+            .into();
+        let quoted_params = zk_wallet
+            .approval_based_paymaster_params(paymaster, token, Bytes::default(), &request)
+            .await
+            .unwrap();
+        era_node.set_fee(fee);
+        let tx_hash = zk_wallet
+            .send_with_approval_based_paymaster(paymaster, token, Bytes::default(), request)
+            .await
+            .unwrap();
+        let sent_transactions = era_node.sent_transactions();
+        let sent = Eip712TransactionRequest::decode(&sent_transactions[0].raw).unwrap();
+        let paymaster_params = sent.custom_data.paymaster_params.clone().unwrap();
+
+        assert_eq!(sent_transactions[0].hash, tx_hash);
+        assert_eq!(sent.recover_signer().unwrap(), zk_wallet.l2_address());
+        assert_eq!(sent.gas_limit, Some(fee.gas_limit));
+        assert_eq!(sent.max_fee_per_gas, Some(fee.max_fee_per_gas));
+        assert_ne!(
+            paymaster_params.paymaster_input,
+            quoted_params.paymaster_input
+        );
+        assert_eq!(
+            paymaster_params.paymaster_input,
+            PaymasterParams::approval_based(
+                paymaster,
+                token,
+                fee.gas_limit * fee.max_fee_per_gas * 2_u64,
+                Bytes::default()
+            )
+            .unwrap()
+            .paymaster_input
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_fee_in_token_bounds() {
+        let era_node = mock_era_node();
+        let token = Address::from_low_u64_be(0x70c);
+        era_node.set_token_price(Address::zero(), "2000");
+        era_node.set_token_price(token, "0.5");
+        let zk_wallet =
+            ZKSWallet::new(mock_wallet(), None, Some(era_node.provider()), None).unwrap();
+
+        era_node.set_call_response(
+            token,
+            id("decimals()"),
+            encode(&[Token::Uint(78_u8.into())]),
+        );
+        assert!(matches!(
+            zk_wallet.fee_in_token(token, 1_u64.into()).await,
+            Err(ZKSWalletError::UnsupportedTokenDecimals { decimals, .. }) if decimals == 78_u64.into()
+        ));
+
+        era_node.set_call_response(
+            token,
+            id("decimals()"),
+            encode(&[Token::Uint(18_u8.into())]),
+        );
+        assert!(matches!(
+            zk_wallet.fee_in_token(token, U256::MAX).await,
+            Err(ZKSWalletError::FeeConversionOverflow { fee, .. }) if fee == U256::MAX
+        ));
+    }
+}
@@ -0,0 +1,331 @@
+mod mock_node_tests {
+
+    use crate::mock::ScriptedReceipt;
+    use crate::tests::utils::*;
+    use crate::zks_provider::types::{
+        CallFrame, CallType, CommonData, TransactionType, ZksTransactionReceipt,
+    };
+    use crate::zks_provider::ZKSProvider;
+    use crate::zks_utils::{CONTRACTS_L1_MESSENGER_ADDR, CONTRACT_DEPLOYER_ADDR, EIP712_TX_TYPE};
+    use crate::zks_wallet::{DeployRequest, ZKSWallet};
+
+    use ethers::signers::Signer;
+
+    use ethers::types::{Address, Bytes, H256, U256};
+    use ethers::utils::id;
+    use serde_json::json;
+    use std::fs::File;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_mock_deploy() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let contract_address: Address = "0x2000000000000000000000000000000000000002"
+            .parse()
+            .unwrap();
+        era_node.set_balance(wallet.address(), ether("10"));
+        era_node.script_receipt(
+            CONTRACT_DEPLOYER_ADDR.parse().unwrap(),
+            ScriptedReceipt::new().contract_address(contract_address),
+        );
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+        let mut contract_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        contract_path.push("src/abi/test_contracts/storage_combined.json");
+        let contract: CompiledContract =
+            serde_json::from_reader(File::open(contract_path).unwrap()).unwrap();
+
+        let deploy_request = DeployRequest::with(contract.abi, contract.bin.to_vec(), vec![])
+            .from(zk_wallet.l2_address());
+        let deployed_address = zk_wallet.deploy(&deploy_request).await.unwrap();
+        let sent_transaction = era_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(deployed_address, contract_address);
+        assert_eq!(sent_transaction.tx_type, EIP712_TX_TYPE);
+        assert_eq!(
+            sent_transaction.to,
+            Some(CONTRACT_DEPLOYER_ADDR.parse().unwrap())
+        );
+    }
+
+    fn call_frame(call_type: CallType, to: Address, calls: Vec<CallFrame>) -> CallFrame {
+        CallFrame {
+            call_type,
+            from: Address::zero(),
+            to: Some(to),
+            value: U256::zero(),
+            gas: 1_000_u64.into(),
+            gas_used: 100_u64.into(),
+            input: Bytes::from(id("transfer(address,uint256)").to_vec()),
+            output: Bytes::default(),
+            error: None,
+            revert_reason: None,
+            calls,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_debug_trace_block() {
+        let era_node = mock_era_node();
+        let provider = era_node.provider();
+        let first_trace = call_frame(
+            CallType::Call,
+            Address::from_low_u64_be(1),
+            vec![call_frame(
+                CallType::DelegateCall,
+                Address::from_low_u64_be(2),
+                vec![],
+            )],
+        );
+        let second_trace = call_frame(CallType::Create, Address::from_low_u64_be(3), vec![]);
+        era_node.set_block_traces(1_u64, vec![first_trace.clone(), second_trace.clone()]);
+
+        let by_number = ZKSProvider::debug_trace_block_by_number(&provider, 1_u64, None)
+            .await
+            .unwrap();
+        let by_hash =
+            ZKSProvider::debug_trace_block_by_hash(&provider, H256::from_low_u64_be(1), None)
+                .await
+                .unwrap();
+
+        assert_eq!(by_number, vec![first_trace.clone(), second_trace.clone()]);
+        assert_eq!(by_hash, by_number);
+        assert!(
+            ZKSProvider::debug_trace_block_by_number(&provider, 2_u64, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_call_frame_helpers() {
+        let mut reverted = call_frame(CallType::StaticCall, Address::from_low_u64_be(4), vec![]);
+        reverted.revert_reason = Some("reverted".to_owned());
+        let trace = call_frame(
+            CallType::Call,
+            Address::from_low_u64_be(1),
+            vec![
+                call_frame(
+                    CallType::Call,
+                    Address::from_low_u64_be(2),
+                    vec![call_frame(
+                        CallType::DelegateCall,
+                        Address::from_low_u64_be(3),
+                        vec![],
+                    )],
+                ),
+                reverted,
+            ],
+        );
+
+        let mut visited = Vec::new();
+        trace.walk(|frame, depth| visited.push((frame.to.unwrap().to_low_u64_be(), depth)));
+        let flattened: Vec<u64> = trace
+            .flatten()
+            .iter()
+            .map(|frame| frame.to.unwrap().to_low_u64_be())
+            .collect();
+        let reverted_frames = trace.filter(CallFrame::is_reverted);
+
+        assert_eq!(visited, vec![(1, 0), (2, 1), (3, 2), (4, 1)]);
+        assert_eq!(flattened, vec![1, 2, 3, 4]);
+        assert_eq!(reverted_frames.len(), 1);
+        assert_eq!(trace.selector(), Some(id("transfer(address,uint256)")));
+    }
+
+    #[test]
+    fn test_call_frame_deserialization() {
+        let trace: CallFrame = serde_json::from_value(json!({
+            "type": "Call",
+            "from": "0x0000000000000000000000000000000000000001",
+            "to": "0x0000000000000000000000000000000000008001",
+            "gas": "0x10",
+            "gasUsed": "0x8",
+            "value": "0x0",
+            "input": "0x",
+            "output": "0x",
+            "calls": [{
+                "type": "STATICCALL",
+                "from": "0x0000000000000000000000000000000000008001",
+                "to": "0x000000000000000000000000000000000000800a",
+                "gas": "0x8",
+                "gasUsed": "0x4",
+                "input": "0x",
+                "error": "out of gas"
+            }, {
+                // callTracer leaves out the callee of failed contract creations.
+                "type": "CREATE",
+                "from": "0x0000000000000000000000000000000000008001",
+                "gas": "0x4",
+                "gasUsed": "0x4",
+                "input": "0x",
+                "error": "contract creation failed"
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(trace.call_type, CallType::Call);
+        assert_eq!(trace.calls.len(), 2);
+        assert_eq!(trace.calls[0].call_type, CallType::StaticCall);
+        assert!(trace.calls[0].is_reverted());
+        assert!(trace.calls[0].calls.is_empty());
+        assert_eq!(trace.calls[1].call_type, CallType::Create);
+        assert_eq!(trace.calls[1].to, None);
+        assert!(trace.calls[1].is_reverted());
+    }
+
+    #[test]
+    fn test_zks_transaction_receipt_deserialization() {
+        let receipt: ZksTransactionReceipt = serde_json::from_value(json!({
+            "blockHash": "0x7a7d8d6b1c6e4c7c46a5d8bb2b7d1a4d0f9b8f2f1c5b8e4f3d2a1b0c9d8e7f6a",
+            "blockNumber": "0x1d1",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0x0",
+            "effectiveGasPrice": "0xee6b280",
+            "from": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+            "gasUsed": "0x1d4c0",
+            "l1BatchNumber": "0x3",
+            "l1BatchTxIndex": "0x2",
+            "l2ToL1Logs": [{
+                "blockHash": "0x7a7d8d6b1c6e4c7c46a5d8bb2b7d1a4d0f9b8f2f1c5b8e4f3d2a1b0c9d8e7f6a",
+                "blockNumber": "0x1d1",
+                "l1BatchNumber": "0x3",
+                "transactionIndex": "0x0",
+                "transactionHash": "0x2c4a1e4f6bbab9b1b1e8d1c3a2f5e7d9c0b8a6f4e2d1c3b5a7f9e0d2c4b6a8f1",
+                "transactionLogIndex": "0x0",
+                "txIndexInL1Batch": "0x2",
+                "shardId": "0x0",
+                "isService": true,
+                "sender": "0x0000000000000000000000000000000000008008",
+                "key": "0x00000000000000000000000036615cf349d7f6344891b1e7ca7c72883f5dc049",
+                "value": "0x1ad4f9e3c3c2e7d1f0f5a0b7a4a0c8e5f3d2b1a0c9e8f7d6c5b4a3f2e1d0c9b8",
+                "logIndex": "0x0"
+            }],
+            "logs": [],
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "root": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "status": "0x1",
+            "to": "0x000000000000000000000000000000000000800a",
+            "transactionHash": "0x2c4a1e4f6bbab9b1b1e8d1c3a2f5e7d9c0b8a6f4e2d1c3b5a7f9e0d2c4b6a8f1",
+            "transactionIndex": "0x0",
+            "type": "0x71"
+        }))
+        .unwrap();
+        let l2_to_l1_log = receipt.l2_to_l1_logs.first().unwrap();
+
+        assert_eq!(receipt.l1_batch_number, Some(3_u64.into()));
+        assert_eq!(receipt.l1_batch_tx_index, Some(2_u64.into()));
+        assert_eq!(receipt.receipt.status, Some(1_u64.into()));
+        assert!(receipt.receipt.other.is_empty());
+        assert!(l2_to_l1_log.is_service);
+        assert_eq!(l2_to_l1_log.tx_number_in_block, Some(2_u64.into()));
+        assert_eq!(
+            l2_to_l1_log.sender,
+            CONTRACTS_L1_MESSENGER_ADDR.parse::<Address>().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_get_raw_block_transactions() {
+        let era_node = mock_era_node();
+        let execute = json!({
+            "contractAddress": "0x000000000000000000000000000000000000800a",
+            "calldata": "0x",
+            "value": "0x1",
+            "factoryDeps": null
+        });
+        era_node
+            .set_response(
+                "zks_getRawBlockTransactions",
+                json!([
+                    {
+                        "common_data": { "L1": {
+                            "sender": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+                            "serialId": 3_u64,
+                            "deadlineBlock": 0_u64,
+                            "layer2TipFee": "0x0",
+                            "fullFee": "0x0",
+                            "maxFeePerGas": "0xee6b280",
+                            "gasLimit": "0x989680",
+                            "gasPerPubdataLimit": "0x320",
+                            "opProcessingType": "Common",
+                            "priorityQueueType": "Deque",
+                            "ethHash": H256::zero(),
+                            "ethBlock": 125_u64,
+                            "canonicalTxHash": H256::repeat_byte(1),
+                            "toMint": "0x1",
+                            "refundRecipient": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049"
+                        }},
+                        "execute": execute,
+                        "received_timestamp_ms": 1_u64
+                    },
+                    {
+                        "common_data": { "L2": {
+                            "nonce": 7_u64,
+                            "fee": {
+                                "gas_limit": "0x1",
+                                "max_fee_per_gas": "0x2",
+                                "max_priority_fee_per_gas": "0x3",
+                                "gas_per_pubdata_limit": "0x4"
+                            },
+                            "initiatorAddress": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+                            "signature": [1_u8, 2_u8, 3_u8],
+                            "transactionType": "EIP712Transaction",
+                            "input": { "hash": H256::repeat_byte(2), "data": [0x71_u8] },
+                            "paymasterParams": {
+                                "paymaster": "0x0000000000000000000000000000000000000000",
+                                "paymasterInput": []
+                            }
+                        }},
+                        "execute": execute,
+                        "received_timestamp_ms": 2_u64,
+                        "raw_bytes": "0x71"
+                    },
+                    {
+                        "common_data": { "ProtocolUpgrade": {
+                            "sender": "0x0000000000000000000000000000000000008007",
+                            "upgradeId": 12_u64,
+                            "maxFeePerGas": "0x0",
+                            "gasLimit": "0x3d0900",
+                            "gasPerPubdataLimit": "0x320",
+                            "ethHash": H256::zero(),
+                            "ethBlock": 0_u64,
+                            "canonicalTxHash": H256::repeat_byte(3),
+                            "toMint": "0x0",
+                            "refundRecipient": "0x0000000000000000000000000000000000000000"
+                        }},
+                        "execute": execute,
+                        "received_timestamp_ms": 3_u64
+                    }
+                ]),
+            )
+            .unwrap();
+
+        let transactions = era_node
+            .provider()
+            .get_raw_block_transactions(1_u64)
+            .await
+            .unwrap();
+
+        assert_eq!(transactions.len(), 3);
+        assert!(matches!(&transactions[0].common_data, CommonData::L1(l1) if l1.serial_id == 3));
+        match &transactions[1].common_data {
+            CommonData::L2(l2) => {
+                assert_eq!(l2.nonce, 7);
+                assert_eq!(l2.fee.max_priority_fee_per_gas, 3_u64.into());
+                assert_eq!(l2.signature, vec![1, 2, 3]);
+                assert_eq!(l2.transaction_type, TransactionType::Eip712);
+                assert!(l2.paymaster_params.paymaster.is_zero());
+            }
+            common_data => panic!("unexpected common data {common_data:?}"),
+        }
+        assert_eq!(transactions[1].raw_bytes, Some(Bytes::from_static(&[0x71])));
+        assert!(matches!(
+            &transactions[2].common_data,
+            CommonData::ProtocolUpgrade(upgrade) if upgrade.upgrade_id == 12
+        ));
+        assert!(transactions[2].execute.factory_deps.is_none());
+    }
+}
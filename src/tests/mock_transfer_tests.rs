@@ -0,0 +1,86 @@
+mod mock_transfer_tests {
+
+    use crate::eip712::Eip712TransactionRequest;
+
+    use crate::tests::utils::*;
+
+    use crate::zks_utils::L2_ETH_TOKEN_ADDRESS;
+    use crate::zks_wallet::{TransferRequest, ZKSWallet};
+    use ethers::abi::{encode, Token};
+    use ethers::providers::Middleware;
+    use ethers::signers::Signer;
+    use ethers::types::{Address, U256};
+    use ethers::utils::id;
+
+    #[tokio::test]
+    async fn test_mock_transfer() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let receiver_address: Address = "0xa61464658AfeAf65CccaaFD3a512b69A83B77618"
+            .parse()
+            .unwrap();
+        era_node.set_balance(wallet.address(), ether("10"));
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+        let amount_to_transfer = ether("1");
+        let request = TransferRequest::new(amount_to_transfer)
+            .to(receiver_address)
+            .from(zk_wallet.l2_address());
+        let tx_hash = zk_wallet.transfer(&request).await.unwrap();
+
+        let receipt = zk_wallet
+            .get_era_provider()
+            .unwrap()
+            .get_transaction_receipt(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let fee = receipt.effective_gas_price.unwrap() * receipt.gas_used.unwrap();
+
+        assert_eq!(receipt.status.unwrap(), 1_u8.into());
+        assert_eq!(era_node.balance(receiver_address), amount_to_transfer);
+        assert_eq!(
+            era_node.balance(zk_wallet.l2_address()),
+            ether("10") - amount_to_transfer - fee
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_erc20_transfer() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let token = Address::from_low_u64_be(0x70c);
+        let receiver_address = Address::from_low_u64_be(0x1234);
+        era_node.set_balance(wallet.address(), ether("10"));
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+        let request = TransferRequest::new(1_000_u64.into())
+            .to(receiver_address)
+            .from(zk_wallet.l2_address())
+            .token(token);
+        zk_wallet.transfer(&request).await.unwrap();
+        zk_wallet.transfer_eip712(&request).await.unwrap();
+        zk_wallet
+            .transfer(&request.clone().token(L2_ETH_TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        let sent_transactions = era_node.sent_transactions();
+        let eip712_transfer = Eip712TransactionRequest::decode(&sent_transactions[1].raw).unwrap();
+        let transfer_data = [
+            &id("transfer(address,uint256)")[..],
+            &encode(&[
+                Token::Address(receiver_address),
+                Token::Uint(1_000_u64.into()),
+            ]),
+        ]
+        .concat();
+
+        assert_eq!(sent_transactions[0].to, Some(token));
+        assert_eq!(sent_transactions[0].value, U256::zero());
+        assert_eq!(sent_transactions[0].data.to_vec(), transfer_data);
+        assert_eq!(eip712_transfer.to, token);
+        assert_eq!(eip712_transfer.data.to_vec(), transfer_data);
+        assert_eq!(sent_transactions[2].to, Some(receiver_address));
+        assert_eq!(era_node.balance(receiver_address), 1_000_u64.into());
+    }
+}
@@ -0,0 +1,265 @@
+mod mock_withdraw_tests {
+
+    use crate::eip712::Eip712TransactionRequest;
+    use crate::mock::ScriptedReceipt;
+    use crate::tests::utils::*;
+    use crate::zks_provider::types::{BridgeContracts, L2ToL1Log, Proof};
+    use crate::zks_provider::ZKSProvider;
+    use crate::zks_utils::{
+        CONTRACTS_L1_MESSENGER_ADDR, CONTRACTS_L2_ETH_TOKEN_ADDR, EIP712_TX_TYPE,
+        L2_ETH_TOKEN_ADDRESS,
+    };
+    use crate::zks_wallet::{WithdrawRequest, WithdrawalStatus, ZKSWallet};
+    use ethers::abi::{encode, Token};
+    use ethers::providers::Middleware;
+    use ethers::signers::Signer;
+
+    use ethers::types::{Address, Bytes, Log, H256, U256};
+    use ethers::utils::{id, keccak256};
+
+    #[tokio::test]
+    async fn test_mock_erc20_withdraw() {
+        let era_node = mock_era_node();
+        let wallet = mock_wallet();
+        let token = Address::from_low_u64_be(0x70c);
+        let default_bridge = Address::from_low_u64_be(0xb1);
+        let custom_bridge = Address::from_low_u64_be(0xb2);
+        era_node.set_balance(wallet.address(), ether("10"));
+        era_node.set_bridge_contracts(BridgeContracts {
+            l1_erc20_default_bridge: Address::from_low_u64_be(0xa1),
+            l2_erc20_default_bridge: default_bridge,
+        });
+
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(era_node.provider()), None).unwrap();
+        let request = WithdrawRequest::new(1_000_u64.into())
+            .to(zk_wallet.l1_address())
+            .from(zk_wallet.l2_address())
+            .token(token);
+        zk_wallet.withdraw(&request).await.unwrap();
+        zk_wallet
+            .withdraw(&request.clone().bridge(custom_bridge))
+            .await
+            .unwrap();
+        let sent_transactions = era_node.sent_transactions();
+        let withdrawal = Eip712TransactionRequest::decode(&sent_transactions[0].raw).unwrap();
+
+        assert_eq!(withdrawal.to, default_bridge);
+        assert_eq!(withdrawal.value, U256::zero());
+        assert_eq!(
+            withdrawal.data.to_vec(),
+            [
+                &id("withdraw(address,address,uint256)")[..],
+                &encode(&[
+                    Token::Address(zk_wallet.l1_address()),
+                    Token::Address(token),
+                    Token::Uint(1_000_u64.into()),
+                ]),
+            ]
+            .concat()
+        );
+        assert_eq!(sent_transactions[1].to, Some(custom_bridge));
+        assert!(Eip712TransactionRequest::try_from(request).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_withdraw_and_finalize() {
+        let era_node = mock_era_node();
+        let eth_node = mock_eth_node();
+        let wallet = mock_wallet();
+        let main_contract: Address = MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap();
+        let messenger: Address = CONTRACTS_L1_MESSENGER_ADDR.parse().unwrap();
+        let message = Bytes::from_static(b"withdrawal message");
+        era_node.set_balance(wallet.address(), ether("10"));
+        eth_node.set_balance(wallet.address(), ether("10"));
+        era_node.set_l1_batch_number(5_u64);
+        era_node.script_receipt(
+            CONTRACTS_L2_ETH_TOKEN_ADDR.parse().unwrap(),
+            ScriptedReceipt::new()
+                .log(Log {
+                    address: messenger,
+                    topics: vec![
+                        H256::from(keccak256("L1MessageSent(address,bytes32,bytes)")),
+                        H256::from(L2_ETH_TOKEN_ADDRESS),
+                    ],
+                    data: encode(&[Token::Bytes(message.to_vec())]).into(),
+                    ..Default::default()
+                })
+                .l2_to_l1_log(L2ToL1Log {
+                    sender: messenger,
+                    ..Default::default()
+                }),
+        );
+
+        let zk_wallet = ZKSWallet::new(
+            wallet,
+            None,
+            Some(era_node.provider()),
+            Some(eth_node.provider()),
+        )
+        .unwrap();
+        let amount_to_withdraw = ether("1");
+        let withdraw_request = WithdrawRequest::new(amount_to_withdraw).to(zk_wallet.l1_address());
+        let tx_hash = zk_wallet.withdraw(&withdraw_request).await.unwrap();
+
+        let receipt = zk_wallet
+            .get_era_provider()
+            .unwrap()
+            .get_transaction_receipt(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let fee = receipt.effective_gas_price.unwrap() * receipt.gas_used.unwrap();
+        let sent_transaction = era_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(sent_transaction.tx_type, EIP712_TX_TYPE);
+        assert_eq!(
+            era_node.balance(zk_wallet.l2_address()),
+            ether("10") - amount_to_withdraw - fee
+        );
+
+        let zks_receipt = zk_wallet
+            .get_era_provider()
+            .unwrap()
+            .get_zks_transaction_receipt(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let l2_to_l1_log = zks_receipt.l2_to_l1_logs.first().unwrap();
+
+        assert_eq!(zks_receipt.receipt.transaction_hash, tx_hash);
+        assert_eq!(zks_receipt.l1_batch_tx_index, Some(0_u64.into()));
+        assert_eq!(l2_to_l1_log.sender, messenger);
+        assert_eq!(l2_to_l1_log.transaction_hash, Some(tx_hash));
+
+        era_node.set_l2_to_l1_log_proof(
+            tx_hash,
+            Proof {
+                id: 0,
+                merkle_proof: vec![H256::zero()],
+                root: Bytes::default(),
+            },
+        );
+        era_node.set_l1_batch_number(9_u64);
+        let set_l1_state = |total_batches_executed: u64, is_finalized: bool| {
+            eth_node.set_call_response(
+                main_contract,
+                id("getTotalBlocksExecuted()"),
+                encode(&[Token::Uint(total_batches_executed.into())]),
+            );
+            eth_node.set_call_response(
+                main_contract,
+                id("isEthWithdrawalFinalized(uint256,uint256)"),
+                encode(&[Token::Bool(is_finalized)]),
+            );
+        };
+        set_l1_state(4, false);
+        assert_eq!(
+            zk_wallet.withdrawal_status(tx_hash).await.unwrap(),
+            WithdrawalStatus::NotExecuted
+        );
+        set_l1_state(5, false);
+        assert_eq!(
+            zk_wallet.withdrawal_status(tx_hash).await.unwrap(),
+            WithdrawalStatus::ReadyToFinalize
+        );
+
+        let finalize_tx_hash = zk_wallet.finalize_withdraw(tx_hash).await.unwrap();
+        set_l1_state(9, true);
+        assert!(zk_wallet.is_withdrawal_finalized(tx_hash).await.unwrap());
+        let finalize_receipt = zk_wallet
+            .get_eth_provider()
+            .unwrap()
+            .get_transaction_receipt(finalize_tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let finalize_transaction = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(finalize_receipt.status.unwrap(), 1_u8.into());
+        assert_eq!(finalize_transaction.to, Some(main_contract));
+        assert_eq!(
+            finalize_transaction.data.get(..4).unwrap(),
+            id("finalizeEthWithdrawal(uint256,uint256,uint16,bytes,bytes32[])")
+        );
+        // The batch of the withdrawal, not the latest one.
+        assert_eq!(
+            finalize_transaction.data.get(4..36).unwrap(),
+            H256::from_low_u64_be(5).as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_finalize_erc20_withdrawal() {
+        let era_node = mock_era_node();
+        let eth_node = mock_eth_node();
+        let wallet = mock_wallet();
+        let messenger: Address = CONTRACTS_L1_MESSENGER_ADDR.parse().unwrap();
+        let l1_bridge = Address::from_low_u64_be(0xa1);
+        let l2_bridge = Address::from_low_u64_be(0xb1);
+        era_node.set_balance(wallet.address(), ether("10"));
+        eth_node.set_balance(wallet.address(), ether("10"));
+        era_node.set_bridge_contracts(BridgeContracts {
+            l1_erc20_default_bridge: l1_bridge,
+            l2_erc20_default_bridge: l2_bridge,
+        });
+        era_node.script_receipt(
+            l2_bridge,
+            ScriptedReceipt::new()
+                .log(Log {
+                    address: messenger,
+                    topics: vec![
+                        H256::from(keccak256("L1MessageSent(address,bytes32,bytes)")),
+                        H256::from(l2_bridge),
+                    ],
+                    data: encode(&[Token::Bytes(b"token withdrawal".to_vec())]).into(),
+                    ..Default::default()
+                })
+                .l2_to_l1_log(L2ToL1Log {
+                    sender: messenger,
+                    ..Default::default()
+                }),
+        );
+
+        let zk_wallet = ZKSWallet::new(
+            wallet,
+            None,
+            Some(era_node.provider()),
+            Some(eth_node.provider()),
+        )
+        .unwrap();
+        let withdraw_request = WithdrawRequest::new(1_000_u64.into())
+            .to(zk_wallet.l1_address())
+            .token(Address::from_low_u64_be(0x70c));
+        let tx_hash = zk_wallet.withdraw(&withdraw_request).await.unwrap();
+        era_node.set_l2_to_l1_log_proof(
+            tx_hash,
+            Proof {
+                id: 0,
+                merkle_proof: vec![H256::zero()],
+                root: Bytes::default(),
+            },
+        );
+        eth_node.set_call_response(
+            MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap(),
+            id("getTotalBlocksExecuted()"),
+            encode(&[Token::Uint(1_u64.into())]),
+        );
+        eth_node.set_call_response(
+            l1_bridge,
+            id("isWithdrawalFinalized(uint256,uint256)"),
+            encode(&[Token::Bool(false)]),
+        );
+        assert_eq!(
+            zk_wallet.withdrawal_status(tx_hash).await.unwrap(),
+            WithdrawalStatus::ReadyToFinalize
+        );
+        zk_wallet.finalize_withdraw(tx_hash).await.unwrap();
+        let finalize_transaction = eth_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(finalize_transaction.to, Some(l1_bridge));
+        assert_eq!(
+            finalize_transaction.data.get(..4).unwrap(),
+            id("finalizeWithdrawal(uint256,uint256,uint16,bytes,bytes32[])")
+        );
+    }
+}
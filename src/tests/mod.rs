@@ -1,3 +1,13 @@
+mod debug_trace_tests;
+mod eip712_tests;
+mod mock_deposit_tests;
+mod mock_paymaster_tests;
+mod mock_tests;
+mod mock_transfer_tests;
+mod mock_withdraw_tests;
 mod provider_tests;
+mod remote_signer_tests;
+mod smart_account_tests;
 mod utils;
+mod verifying_paymaster_tests;
 mod wallet_tests;
@@ -363,7 +363,7 @@ mod zks_provider_tests {
         let transfer_request = TransferRequest::new(1_u64.into())
             .to(Address::from_str("0x36615Cf349d7F6344891B1e7CA7C72883F5dc049").unwrap())
             .from(zk_wallet.l2_address());
-        let transaction_hash = zk_wallet.transfer(&transfer_request, None).await.unwrap();
+        let transaction_hash = zk_wallet.transfer(&transfer_request).await.unwrap();
         let invalid_transaction_hash: H256 =
             "0x84472204e445cb3cd5f3ce5e23abcc2892cda5e61b35855a7f0bb1562a6e30e7"
                 .parse()
@@ -750,7 +750,7 @@ mod zks_provider_tests {
         let transfer_request = TransferRequest::new(1_u64.into())
             .to(Address::from_str("0x36615Cf349d7F6344891B1e7CA7C72883F5dc049").unwrap())
             .from(zk_wallet.l2_address());
-        let transaction_hash = zk_wallet.transfer(&transfer_request, None).await.unwrap();
+        let transaction_hash = zk_wallet.transfer(&transfer_request).await.unwrap();
         let invalid_transaction_hash: H256 =
             "0x84472204e445cb3cd5f3ce5e23abcc2892cda5e61b35855a7f0bb1562a6e30e7"
                 .parse()
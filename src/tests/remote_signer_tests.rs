@@ -0,0 +1,240 @@
+mod remote_signer_tests {
+    use crate::eip712::{
+        Eip712Meta, Eip712Transaction, Eip712TransactionRequest, Eip712TransactionSigner,
+    };
+    use crate::remote_signer::{RemoteSigner, RemoteSignerError};
+    use crate::tests::utils::*;
+    use crate::zks_utils::{ERA_CHAIN_ID, ETH_CHAIN_ID};
+    use crate::zks_wallet::{TransferRequest, ZKSWallet};
+    use ethers::providers::{Http, Provider};
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::transaction::eip2718::TypedTransaction;
+    use ethers::types::transaction::eip712::{Eip712, TypedData};
+    use ethers::types::{Address, Bytes, Eip1559TransactionRequest, H256, U256};
+    use ethers::utils::hash_message;
+    use serde_json::{json, Value};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::str::FromStr;
+
+    fn wallet() -> LocalWallet {
+        LocalWallet::from_str("0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959")
+            .unwrap()
+            .with_chain_id(ERA_CHAIN_ID)
+    }
+
+    /// Serves the signing methods of a remote signer over HTTP, signing with `wallet`.
+    fn signing_service(wallet: LocalWallet) -> Provider<Http> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                serve_request(&wallet, stream);
+            }
+        });
+        Provider::try_from(url).unwrap()
+    }
+
+    fn serve_request(wallet: &LocalWallet, mut stream: TcpStream) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0_u8; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap() {
+            "eth_sign" => {
+                let message: Bytes = serde_json::from_value(params[1].clone()).unwrap();
+                json!(Bytes::from(
+                    wallet.sign_hash(hash_message(message)).unwrap().to_vec()
+                ))
+            }
+            "eth_signTransaction" => {
+                let tx: TypedTransaction = serde_json::from_value(params[0].clone()).unwrap();
+                let signature = wallet.sign_transaction_sync(&tx).unwrap();
+                json!({ "raw": tx.rlp_signed(&signature), "tx": tx })
+            }
+            "eth_signTypedData_v4" => {
+                let typed_data: TypedData = serde_json::from_value(params[1].clone()).unwrap();
+                let digest = H256(typed_data.encode_eip712().unwrap());
+                json!(Bytes::from(wallet.sign_hash(digest).unwrap().to_vec()))
+            }
+            method => panic!("unexpected method {method}"),
+        };
+
+        let response =
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+    }
+
+    fn transaction(from: Address) -> Eip712Transaction {
+        Eip712TransactionRequest::new()
+            .from(from)
+            .to(Address::from_low_u64_be(0x1234))
+            .nonce(1_u64)
+            .value(1_000_u64)
+            .data(vec![0xde, 0xad, 0xbe, 0xef])
+            .max_priority_fee_per_gas(1_u64)
+            .max_fee_per_gas(250_000_000_u64)
+            .gas_limit(1_000_000_u64)
+            .chain_id(ERA_CHAIN_ID)
+            .custom_data(Eip712Meta::new().factory_deps(vec![vec![0_u8; 32]]))
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_transaction_typed_data() {
+        let transaction = transaction(Address::from_low_u64_be(1));
+        let typed_data = transaction.typed_data().unwrap();
+        let json = serde_json::to_value(&typed_data).unwrap();
+
+        assert_eq!(
+            typed_data.encode_eip712().unwrap(),
+            transaction.encode_eip712().unwrap()
+        );
+        assert_eq!(json["primaryType"], "Transaction");
+        assert_eq!(json["domain"]["name"], "zkSync");
+        assert_eq!(json["types"]["EIP712Domain"][2]["name"], "chainId");
+        assert_eq!(json["message"]["data"], "0xdeadbeef");
+        assert!(json["message"].get("chainId").is_none());
+        assert_eq!(
+            serde_json::from_value::<TypedData>(json).unwrap(),
+            typed_data
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remote_signatures() {
+        let wallet = wallet();
+        let signer = RemoteSigner::new(signing_service(wallet.clone()), wallet.address())
+            .with_chain_id(ERA_CHAIN_ID);
+        let transaction = transaction(wallet.address());
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(wallet.address())
+            .chain_id(ERA_CHAIN_ID)
+            .to(Address::from_low_u64_be(0x1234))
+            .value(1_u64)
+            .nonce(0_u64)
+            .gas(21_000_u64)
+            .max_fee_per_gas(2_u64)
+            .max_priority_fee_per_gas(1_u64)
+            .into();
+
+        assert_eq!(
+            signer.sign_message("hello").await.unwrap(),
+            wallet.sign_message("hello").await.unwrap()
+        );
+        assert_eq!(
+            signer.sign_transaction_eip712(&transaction).await.unwrap(),
+            wallet.sign_typed_data(&transaction).await.unwrap()
+        );
+        assert_eq!(
+            signer
+                .sign_transaction(&tx)
+                .await
+                .unwrap()
+                .recover(tx.sighash())
+                .unwrap(),
+            wallet.address()
+        );
+        assert!(matches!(
+            signer
+                .sign_typed_data(&transaction.typed_data().unwrap())
+                .await,
+            Err(RemoteSignerError::UnsupportedTypedData)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remote_signature_from_unexpected_signer() {
+        let wallet = wallet();
+        let signer = RemoteSigner::new(
+            signing_service(LocalWallet::new(&mut ethers::core::rand::thread_rng())),
+            wallet.address(),
+        );
+
+        assert!(matches!(
+            signer.sign_message("hello").await,
+            Err(RemoteSignerError::UnexpectedSigner { expected, .. }) if expected == wallet.address()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_wallet() {
+        let era_node = mock_era_node();
+        let wallet = wallet();
+        let signer = RemoteSigner::new(signing_service(wallet.clone()), wallet.address())
+            .with_chain_id(ERA_CHAIN_ID);
+        let receiver_address = Address::from_low_u64_be(0x1234);
+        era_node.set_balance(wallet.address(), U256::exp10(18));
+
+        let zk_wallet = ZKSWallet::new(signer, None, Some(era_node.provider()), None).unwrap();
+        let request = TransferRequest::new(1_u64.into())
+            .to(receiver_address)
+            .from(zk_wallet.l2_address());
+        zk_wallet.transfer_eip712(&request).await.unwrap();
+        zk_wallet.transfer(&request).await.unwrap();
+        let sent_transactions = era_node.sent_transactions();
+        let decoded = Eip712TransactionRequest::decode(&sent_transactions[0].raw).unwrap();
+
+        assert_eq!(decoded.recover_signer().unwrap(), wallet.address());
+        assert_eq!(sent_transactions[1].from, wallet.address());
+        assert_eq!(era_node.balance(receiver_address), 2_u64.into());
+    }
+
+    #[tokio::test]
+    async fn test_remote_l2_signer_with_local_l1_key() {
+        let era_node = mock_era_node();
+        let eth_node = mock_eth_node();
+        let wallet = wallet();
+        let l1_wallet =
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()).with_chain_id(ETH_CHAIN_ID);
+        let signer = RemoteSigner::new(signing_service(wallet.clone()), wallet.address())
+            .with_chain_id(ERA_CHAIN_ID);
+        era_node.set_balance(wallet.address(), U256::exp10(18));
+        eth_node.set_balance(l1_wallet.address(), 5_u64);
+
+        let zk_wallet: ZKSWallet<_, LocalWallet, RemoteSigner> = ZKSWallet::from_signers(
+            signer,
+            l1_wallet.clone(),
+            Some(era_node.provider()),
+            Some(eth_node.provider()),
+        )
+        .unwrap();
+        let request = TransferRequest::new(1_u64.into())
+            .to(Address::from_low_u64_be(0x1234))
+            .from(zk_wallet.l2_address());
+        zk_wallet.transfer_eip712(&request).await.unwrap();
+        let sent_transaction = era_node.sent_transactions().pop().unwrap();
+
+        assert_eq!(zk_wallet.l1_address(), l1_wallet.address());
+        assert_eq!(zk_wallet.eth_balance().await.unwrap(), 5_u64.into());
+        assert_eq!(
+            Eip712TransactionRequest::decode(&sent_transaction.raw)
+                .unwrap()
+                .recover_signer()
+                .unwrap(),
+            wallet.address()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+mod smart_account_tests {
+    use crate::eip712::Eip712TransactionRequest;
+    use crate::mock::ScriptedReceipt;
+    use crate::tests::utils::*;
+    use crate::zks_utils::{CONTRACT_DEPLOYER_ADDR, ERA_CHAIN_ID};
+    use crate::zks_wallet::{
+        CallRequest, DeployRequest, MultisigSignatureProvider, TransferRequest, ZKSWallet,
+    };
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::{Address, Signature, H256, U256};
+    use ethers::utils::id;
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn wallet(private_key: &str) -> LocalWallet {
+        LocalWallet::from_str(private_key)
+            .unwrap()
+            .with_chain_id(ERA_CHAIN_ID)
+    }
+
+    fn account_deploy_request() -> DeployRequest {
+        let mut contract_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        contract_path.push("src/abi/test_contracts/storage_combined.json");
+        let contract: CompiledContract =
+            serde_json::from_reader(File::open(contract_path).unwrap()).unwrap();
+        DeployRequest::with(contract.abi, contract.bin.to_vec(), vec![])
+    }
+
+    #[tokio::test]
+    async fn test_deploy_account_and_send_from_it() {
+        let era_node = mock_era_node();
+        let deployer = wallet("0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959");
+        let owners = vec![
+            wallet("0x7726827caac94a7f9e1b160f7ea819f172f7b6f9d2a97f992c38edeab82d4110"),
+            wallet("0xac1e735be8536c6534bb4f17f06f6afc73b2b5ba84ac2cfb12f7461b20c0bbe3"),
+        ];
+        let account_address = Address::from_low_u64_be(0xacc);
+        let receiver_address = Address::from_low_u64_be(0x1234);
+        era_node.set_balance(deployer.address(), U256::exp10(18));
+        era_node.set_balance(account_address, U256::exp10(18));
+        era_node.script_receipt(
+            CONTRACT_DEPLOYER_ADDR.parse().unwrap(),
+            ScriptedReceipt::new().contract_address(account_address),
+        );
+        let zk_wallet = ZKSWallet::new(deployer, None, Some(era_node.provider()), None).unwrap();
+
+        let account = zk_wallet
+            .deploy_account(
+                &account_deploy_request(),
+                MultisigSignatureProvider::new(owners.clone()),
+            )
+            .await
+            .unwrap();
+        account
+            .transfer(&TransferRequest::new(1_000_u64.into()).to(receiver_address))
+            .await
+            .unwrap();
+        account
+            .send(&CallRequest::new(
+                receiver_address,
+                "function setValue(uint256 value)".to_owned(),
+            ))
+            .await
+            .unwrap();
+
+        let sent_transactions = era_node.sent_transactions();
+        let deployment = Eip712TransactionRequest::decode(&sent_transactions[0].raw).unwrap();
+        let transfer = Eip712TransactionRequest::decode(&sent_transactions[1].raw).unwrap();
+        let call = Eip712TransactionRequest::decode(&sent_transactions[2].raw).unwrap();
+        let digest = transfer.signing_digest().unwrap();
+        let custom_signature = transfer.custom_data.custom_signature.clone().unwrap();
+
+        assert_eq!(account.address, account_address);
+        assert_eq!(
+            deployment.data.get(..4).unwrap(),
+            &id("createAccount(bytes32,bytes32,bytes,uint8)")
+        );
+        assert_eq!(transfer.from, account_address);
+        assert_eq!(transfer.to, receiver_address);
+        assert_eq!(custom_signature.len(), 130);
+        for (owner, signature) in owners.iter().zip(custom_signature.chunks(65)) {
+            assert_eq!(
+                Signature::try_from(signature)
+                    .unwrap()
+                    .recover(digest)
+                    .unwrap(),
+                owner.address()
+            );
+        }
+        assert_eq!(sent_transactions[1].hash, transfer.tx_hash().unwrap());
+        assert_eq!(call.from, account_address);
+        assert_eq!(call.data.as_ref(), &id("setValue(uint256)"));
+        assert_eq!(era_node.balance(receiver_address), 1_000_u64.into());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_account_with_create2() {
+        let era_node = mock_era_node();
+        let deployer = wallet("0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959");
+        let owner = deployer.clone();
+        era_node.set_balance(deployer.address(), U256::exp10(18));
+        era_node.script_receipt(
+            CONTRACT_DEPLOYER_ADDR.parse().unwrap(),
+            ScriptedReceipt::new().contract_address(Address::from_low_u64_be(0xacc)),
+        );
+        let zk_wallet = ZKSWallet::new(deployer, None, Some(era_node.provider()), None).unwrap();
+
+        zk_wallet
+            .deploy_account(&account_deploy_request().salt(H256::repeat_byte(1)), owner)
+            .await
+            .unwrap();
+        let deployment =
+            Eip712TransactionRequest::decode(&era_node.sent_transactions()[0].raw).unwrap();
+
+        assert_eq!(
+            deployment.data.get(..4).unwrap(),
+            &id("create2Account(bytes32,bytes32,bytes,uint8)")
+        );
+        assert_eq!(
+            deployment.data.get(4..36).unwrap(),
+            H256::repeat_byte(1).as_bytes()
+        );
+    }
+}
@@ -1,16 +1,28 @@
-use std::env;
+use std::{env, str::FromStr};
 
 use ethers::{
-    abi::Abi,
+    abi::{encode, Abi, Token, Tokenizable},
+    contract::EthEvent,
     prelude::{MiddlewareBuilder, SignerMiddleware},
     providers::{Http, Provider},
     signers::{LocalWallet, Signer, Wallet},
-    types::Bytes,
+    types::{Address, Bytes, Log, H256, U256},
+    utils::{id, parse_units},
 };
 use ethers_contract::core::k256::ecdsa::SigningKey;
 use serde::Deserialize;
 
-use crate::zks_utils::ERA_CHAIN_ID;
+use crate::{
+    contracts::main_contract::{L2CanonicalTransaction, NewPriorityRequestFilter},
+    mock::{MockZKSNode, ScriptedReceipt},
+    zks_utils::{ERA_CHAIN_ID, ETH_CHAIN_ID},
+    zks_wallet::ZKSWallet,
+};
+
+pub const MOCK_MAIN_CONTRACT_ADDRESS: &str = "0x1000000000000000000000000000000000000001";
+const MOCK_PRIVATE_KEY: &str = "0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959";
+
+pub type MockWallet = ZKSWallet<Provider<MockZKSNode>, LocalWallet>;
 
 #[derive(Deserialize)]
 pub(crate) struct CompiledContract {
@@ -44,3 +56,73 @@ pub fn era_signer() -> SignerMiddleware<Provider<ethers::providers::Http>, Walle
     );
     era_provider().with_signer(signer)
 }
+
+pub fn mock_era_node() -> MockZKSNode {
+    let node = MockZKSNode::new(ERA_CHAIN_ID);
+    node.set_l1_chain_id(ETH_CHAIN_ID);
+    node.set_main_contract(MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap());
+    node
+}
+
+pub fn mock_eth_node() -> MockZKSNode {
+    MockZKSNode::new(ETH_CHAIN_ID)
+}
+
+pub fn mock_wallet() -> LocalWallet {
+    LocalWallet::from_str(MOCK_PRIVATE_KEY)
+        .unwrap()
+        .with_chain_id(ERA_CHAIN_ID)
+}
+
+pub fn ether(amount: &str) -> U256 {
+    parse_units(amount, "ether").unwrap().into()
+}
+
+pub fn new_priority_request_log(main_contract: Address, l2_tx_hash: H256) -> Log {
+    Log {
+        address: main_contract,
+        topics: vec![NewPriorityRequestFilter::signature()],
+        data: encode(&[
+            Token::Uint(0_u64.into()),
+            Token::FixedBytes(l2_tx_hash.as_bytes().to_vec()),
+            Token::Uint(0_u64.into()),
+            L2CanonicalTransaction::default().into_token(),
+            Token::Array(vec![]),
+        ])
+        .into(),
+        ..Default::default()
+    }
+}
+
+/// Returns an L2 node, an L1 node and a wallet connected to both, with 10 ether on L1, for
+/// L1 to L2 requests: the main contract charges `base_cost` and transactions sent to
+/// `requester`, the main contract or a bridge, request the L2 transaction `l2_tx_hash`.
+pub fn mock_l1_to_l2_setup(
+    base_cost: U256,
+    requester: Address,
+    l2_tx_hash: H256,
+) -> (MockZKSNode, MockZKSNode, MockWallet) {
+    let era_node = mock_era_node();
+    let eth_node = mock_eth_node();
+    let wallet = mock_wallet();
+    let main_contract: Address = MOCK_MAIN_CONTRACT_ADDRESS.parse().unwrap();
+    eth_node.set_balance(wallet.address(), ether("10"));
+    eth_node.set_call_response(
+        main_contract,
+        id("l2TransactionBaseCost(uint256,uint256,uint256)"),
+        encode(&[Token::Uint(base_cost)]),
+    );
+    eth_node.script_receipt(
+        requester,
+        ScriptedReceipt::new().log(new_priority_request_log(main_contract, l2_tx_hash)),
+    );
+
+    let zk_wallet = ZKSWallet::new(
+        wallet,
+        None,
+        Some(era_node.provider()),
+        Some(eth_node.provider()),
+    )
+    .unwrap();
+    (era_node, eth_node, zk_wallet)
+}
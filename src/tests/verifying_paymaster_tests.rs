@@ -0,0 +1,146 @@
+mod verifying_paymaster_tests {
+    use crate::eip712::Eip712TransactionRequest;
+    use crate::verifying_paymaster::{
+        verify_paymaster_input, Sponsorship, VerifyingPaymasterError, VerifyingPaymasterSigner,
+    };
+    use crate::zks_utils::ERA_CHAIN_ID;
+    use ethers::abi::{encode, Token};
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::{Address, U256};
+    use ethers::utils::id;
+    use std::str::FromStr;
+
+    const VALID_AFTER: u64 = 1_700_000_000;
+    const VALID_UNTIL: u64 = 1_700_000_600;
+
+    fn sponsor() -> LocalWallet {
+        LocalWallet::from_str("0x7726827caac94a7f9e1b160f7ea819f172f7b6f9d2a97f992c38edeab82d4110")
+            .unwrap()
+    }
+
+    fn request() -> Eip712TransactionRequest {
+        Eip712TransactionRequest::new()
+            .from(Address::from_low_u64_be(0xa11ce))
+            .to(Address::from_low_u64_be(0x1234))
+            .nonce(7_u64)
+            .value(1_000_u64)
+            .data(vec![0xde, 0xad, 0xbe, 0xef])
+            .chain_id(ERA_CHAIN_ID)
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify_sponsorship() {
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let signer = VerifyingPaymasterSigner::new(paymaster, sponsor());
+        let params = signer
+            .paymaster_params(&request(), VALID_UNTIL, VALID_AFTER)
+            .await
+            .unwrap();
+        let paymaster_input = params.paymaster_input.unwrap();
+
+        let sponsorship = verify_paymaster_input(
+            &request(),
+            paymaster,
+            &paymaster_input,
+            sponsor().address(),
+            VALID_AFTER + 1,
+        )
+        .unwrap();
+
+        assert_eq!(params.paymaster, Some(paymaster));
+        assert_eq!(paymaster_input.get(..4).unwrap(), &id("general(bytes)"));
+        assert_eq!(sponsorship.valid_until, VALID_UNTIL);
+        assert_eq!(sponsorship.valid_after, VALID_AFTER);
+        assert_eq!(
+            Sponsorship::decode(&sponsorship.paymaster_input().unwrap()).unwrap(),
+            sponsorship
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_sponsorship_of_another_transaction() {
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let signer = VerifyingPaymasterSigner::new(paymaster, sponsor());
+        let paymaster_input = signer
+            .sign(&request(), VALID_UNTIL, VALID_AFTER)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            verify_paymaster_input(
+                &request().value(2_000_u64),
+                paymaster,
+                &paymaster_input,
+                sponsor().address(),
+                VALID_AFTER,
+            ),
+            Err(VerifyingPaymasterError::UnexpectedSigner { expected, .. }) if expected == sponsor().address()
+        ));
+        assert!(matches!(
+            verify_paymaster_input(
+                &request(),
+                Address::from_low_u64_be(0xbad),
+                &paymaster_input,
+                sponsor().address(),
+                VALID_AFTER,
+            ),
+            Err(VerifyingPaymasterError::UnexpectedSigner { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reject_sponsorship_outside_validity_window() {
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let signer = VerifyingPaymasterSigner::new(paymaster, sponsor());
+        let paymaster_input = signer
+            .sign(&request(), VALID_UNTIL, VALID_AFTER)
+            .await
+            .unwrap();
+
+        for timestamp in [VALID_AFTER - 1, VALID_UNTIL + 1] {
+            assert!(matches!(
+                verify_paymaster_input(
+                    &request(),
+                    paymaster,
+                    &paymaster_input,
+                    sponsor().address(),
+                    timestamp,
+                ),
+                Err(VerifyingPaymasterError::OutsideValidityWindow { .. })
+            ));
+        }
+        assert!(matches!(
+            Sponsorship::decode(&[0_u8; 4]),
+            Err(VerifyingPaymasterError::InvalidPaymasterInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reject_oversized_validity_bounds() {
+        let paymaster = Address::from_low_u64_be(0xfee);
+        let signer = VerifyingPaymasterSigner::new(paymaster, sponsor());
+        let oversized_input = |valid_until: U256| {
+            let inner_input = encode(&[
+                Token::Uint(valid_until),
+                Token::Uint(VALID_AFTER.into()),
+                Token::Bytes(vec![0_u8; 65]),
+            ]);
+            [
+                id("general(bytes)").to_vec(),
+                encode(&[Token::Bytes(inner_input)]),
+            ]
+            .concat()
+        };
+
+        for valid_until in [U256::from(1_u64 << 48_u32), U256::from(u64::MAX) + 1_u64] {
+            assert!(matches!(
+                Sponsorship::decode(&oversized_input(valid_until)),
+                Err(VerifyingPaymasterError::InvalidPaymasterInput(_))
+            ));
+        }
+        assert!(matches!(
+            signer.sign(&request(), 1_u64 << 48_u32, VALID_AFTER).await,
+            Err(VerifyingPaymasterError::ValidityBoundOutOfRange(bound)) if bound == 1_u64 << 48_u32
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 mod zks_signer_tests {
+    use crate::contracts::main_contract::decode_new_priority_request;
     use crate::tests::utils::*;
     use crate::zks_provider::ZKSProvider;
     use crate::zks_utils::{ERA_CHAIN_ID, ETH_CHAIN_ID};
@@ -9,8 +10,8 @@ mod zks_signer_tests {
     use ethers::contract::abigen;
     use ethers::providers::Middleware;
     use ethers::signers::{LocalWallet, Signer};
-    use ethers::types::Address;
     use ethers::types::U256;
+    use ethers::types::{Address, H256};
     use ethers::utils::parse_units;
     use std::fs::File;
     use std::path::PathBuf;
@@ -54,7 +55,7 @@ mod zks_signer_tests {
         let request = TransferRequest::new(amount_to_transfer)
             .to(receiver_address)
             .from(zk_wallet.l2_address());
-        let tx_hash = zk_wallet.transfer(&request, None).await.unwrap();
+        let tx_hash = zk_wallet.transfer(&request).await.unwrap();
 
         let receipt = era_provider
             .get_transaction_receipt(tx_hash)
@@ -113,18 +114,16 @@ mod zks_signer_tests {
         println!("L1 balance before: {l1_balance_before}");
         println!("L2 balance before: {l2_balance_before}");
 
-        let tx_hash = zk_wallet.deposit(&request).await.unwrap();
+        let deposit = zk_wallet.deposit(&request).await.unwrap();
         let receipt = l1_provider
-            .get_transaction_receipt(tx_hash)
+            .get_transaction_receipt(deposit.l1_tx_hash)
             .await
             .unwrap()
             .unwrap();
         assert_eq!(receipt.status.unwrap(), 1_u8.into());
 
-        let _l2_receipt = l2_provider
-            .get_transaction_receipt(receipt.transaction_hash)
-            .await
-            .unwrap();
+        let l2_receipt = deposit.l2_receipt().await.unwrap();
+        assert_eq!(l2_receipt.status.unwrap(), 1_u8.into());
 
         let l1_balance_after = zk_wallet.eth_balance().await.unwrap();
         let l2_balance_after = zk_wallet.era_balance().await.unwrap();
@@ -141,6 +140,46 @@ mod zks_signer_tests {
         );
     }
 
+    /// The L2 hash of a deposit, computed from the `NewPriorityRequest` event of a real L1
+    /// receipt, must be the hash the L2 node executes the deposit under.
+    #[tokio::test]
+    async fn test_l2_canonical_transaction_hash() {
+        let private_key = "0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959";
+        let request = DepositRequest::new(parse_units("0.01", "ether").unwrap().into());
+
+        let l1_provider = eth_provider();
+        let l2_provider = era_provider();
+        let wallet = LocalWallet::from_str(private_key)
+            .unwrap()
+            .with_chain_id(ERA_CHAIN_ID);
+        let zk_wallet = ZKSWallet::new(
+            wallet,
+            None,
+            Some(l2_provider.clone()),
+            Some(l1_provider.clone()),
+        )
+        .unwrap();
+
+        let deposit = zk_wallet.deposit(&request).await.unwrap();
+        let main_contract = l2_provider.get_main_contract().await.unwrap();
+        let receipt = l1_provider
+            .get_transaction_receipt(deposit.l1_tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let priority_request = receipt
+            .logs
+            .iter()
+            .filter(|log| log.address == main_contract)
+            .find_map(|log| decode_new_priority_request(log).ok())
+            .unwrap();
+
+        let l2_tx_hash = priority_request.transaction.hash();
+        assert_eq!(l2_tx_hash, H256(priority_request.tx_hash));
+        let l2_receipt = deposit.l2_receipt().await.unwrap();
+        assert_eq!(l2_receipt.transaction_hash, l2_tx_hash);
+    }
+
     #[tokio::test]
     async fn test_deposit_to_another_address() {
         let private_key = "0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959";
@@ -168,18 +207,16 @@ mod zks_signer_tests {
         println!("L1 balance before: {l1_balance_before}");
         println!("L2 balance before: {l2_balance_before}");
 
-        let tx_hash = zk_wallet.deposit(&request).await.unwrap();
+        let deposit = zk_wallet.deposit(&request).await.unwrap();
         let receipt = l1_provider
-            .get_transaction_receipt(tx_hash)
+            .get_transaction_receipt(deposit.l1_tx_hash)
             .await
             .unwrap()
             .unwrap();
         assert_eq!(receipt.status.unwrap(), 1_u8.into());
 
-        let _l2_receipt = l2_provider
-            .get_transaction_receipt(receipt.transaction_hash)
-            .await
-            .unwrap();
+        let l2_receipt = deposit.l2_receipt().await.unwrap();
+        assert_eq!(l2_receipt.status.unwrap(), 1_u8.into());
 
         let l1_balance_after = zk_wallet.eth_balance().await.unwrap();
         let l2_balance_after = era_provider().get_balance(to, None).await.unwrap();
@@ -226,7 +263,7 @@ mod zks_signer_tests {
 
         let request = DepositRequest::new(amount).token(Some(token_l1_address));
 
-        let l1_tx_hash = zk_wallet.deposit(&request).await.unwrap();
+        let l1_tx_hash = zk_wallet.deposit(&request).await.unwrap().l1_tx_hash;
         let l1_receipt = zk_wallet
             .get_eth_provider()
             .unwrap()
@@ -276,10 +313,7 @@ mod zks_signer_tests {
         let transfer_request = TransferRequest::new(amount_to_transfer)
             .to(receiver_address)
             .from(zk_wallet.l2_address());
-        let tx_hash = zk_wallet
-            .transfer_eip712(&transfer_request, None)
-            .await
-            .unwrap();
+        let tx_hash = zk_wallet.transfer_eip712(&transfer_request).await.unwrap();
 
         let receipt = era_provider
             .get_transaction_receipt(tx_hash)
@@ -0,0 +1,26 @@
+use ethers::types::{Address, SignatureError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum VerifyingPaymasterError {
+    #[error("ABI error: {0}")]
+    AbiError(#[from] ethers::abi::Error),
+    #[error("Signature error: {0}")]
+    SignatureError(#[from] SignatureError),
+    #[error("Signer error: {0}")]
+    SignerError(String),
+    #[error("Invalid paymaster input: {0}")]
+    InvalidPaymasterInput(String),
+    #[error("Validity bound {0} does not fit in a uint48")]
+    ValidityBoundOutOfRange(u64),
+    #[error("Sponsorship signed by {recovered:?} instead of {expected:?}")]
+    UnexpectedSigner {
+        expected: Address,
+        recovered: Address,
+    },
+    #[error("Sponsorship is only valid from {valid_after} to {valid_until}, not at {timestamp}")]
+    OutsideValidityWindow {
+        valid_after: u64,
+        valid_until: u64,
+        timestamp: u64,
+    },
+}
@@ -0,0 +1,7 @@
+mod errors;
+pub use errors::VerifyingPaymasterError;
+
+mod signer;
+pub use signer::{
+    sponsorship_digest, verify_paymaster_input, Sponsorship, VerifyingPaymasterSigner,
+};
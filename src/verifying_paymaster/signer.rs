@@ -0,0 +1,202 @@
+use super::VerifyingPaymasterError;
+use crate::{abi, eip712::Eip712TransactionRequest, eip712::PaymasterParams};
+use ethers::{
+    abi::{decode, encode, ParamType, Token},
+    signers::Signer,
+    types::{Address, Bytes, Signature, H256, U256},
+    utils::{hash_message, keccak256},
+};
+
+/// Largest validity bound, which the paymaster reads as a `uint48`.
+const MAX_VALIDITY_BOUND: u64 = (1 << 48) - 1;
+
+/// The sponsorship of a transaction by a verifying paymaster, carried in its `paymaster_input`
+/// as `general(abi.encode(uint48 validUntil, uint48 validAfter, bytes signature))`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sponsorship {
+    pub valid_until: u64,
+    pub valid_after: u64,
+    pub signature: Signature,
+}
+
+impl Sponsorship {
+    pub fn paymaster_input(&self) -> Result<Bytes, VerifyingPaymasterError> {
+        check_validity_bounds(self.valid_until, self.valid_after)?;
+        let inner_input = encode(&[
+            Token::Uint(self.valid_until.into()),
+            Token::Uint(self.valid_after.into()),
+            Token::Bytes(self.signature.to_vec()),
+        ]);
+        Ok(abi::paymaster_flow_contract()?
+            .function("general")?
+            .encode_input(&[Token::Bytes(inner_input)])?
+            .into())
+    }
+
+    pub fn decode(paymaster_input: &[u8]) -> Result<Self, VerifyingPaymasterError> {
+        let invalid_input =
+            |reason: &str| VerifyingPaymasterError::InvalidPaymasterInput(reason.to_owned());
+        let general = abi::paymaster_flow_contract()?;
+        let general = general.function("general")?;
+        let (selector, input) = (paymaster_input.get(..4), paymaster_input.get(4..));
+        if selector != Some(general.short_signature().as_slice()) {
+            return Err(invalid_input("not a general paymaster flow"));
+        }
+        let inner_input = general
+            .decode_input(input.unwrap_or_default())?
+            .pop()
+            .and_then(Token::into_bytes)
+            .ok_or(invalid_input("missing inner input"))?;
+
+        let mut tokens = decode(
+            &[ParamType::Uint(48), ParamType::Uint(48), ParamType::Bytes],
+            &inner_input,
+        )?
+        .into_iter();
+        let mut next_uint = || {
+            tokens
+                .next()
+                .and_then(Token::into_uint)
+                .ok_or(invalid_input("missing validity window"))
+                .and_then(|value| {
+                    if value > U256::from(MAX_VALIDITY_BOUND) {
+                        return Err(invalid_input("validity bound does not fit in a uint48"));
+                    }
+                    Ok(value.low_u64())
+                })
+        };
+        let valid_until = next_uint()?;
+        let valid_after = next_uint()?;
+        let signature = tokens
+            .next()
+            .and_then(Token::into_bytes)
+            .ok_or(invalid_input("missing signature"))?;
+
+        Ok(Self {
+            valid_until,
+            valid_after,
+            signature: Signature::try_from(signature.as_slice())?,
+        })
+    }
+}
+
+fn check_validity_bounds(
+    valid_until: u64,
+    valid_after: u64,
+) -> Result<(), VerifyingPaymasterError> {
+    match [valid_until, valid_after]
+        .into_iter()
+        .find(|bound| *bound > MAX_VALIDITY_BOUND)
+    {
+        Some(bound) => Err(VerifyingPaymasterError::ValidityBoundOutOfRange(bound)),
+        None => Ok(()),
+    }
+}
+
+/// The hash signed by the sponsor of `request`:
+///
+/// `keccak256(abi.encode(paymaster, chainId, from, to, value, keccak256(data), nonce, validUntil, validAfter))`
+///
+/// Gas fields are left out so that the fee can be estimated with the final paymaster input.
+pub fn sponsorship_digest(
+    request: &Eip712TransactionRequest,
+    paymaster: Address,
+    valid_until: u64,
+    valid_after: u64,
+) -> H256 {
+    H256(keccak256(encode(&[
+        Token::Address(paymaster),
+        Token::Uint(request.chain_id),
+        Token::Address(request.from),
+        Token::Address(request.to),
+        Token::Uint(request.value),
+        Token::FixedBytes(keccak256(&request.data).to_vec()),
+        Token::Uint(request.nonce),
+        Token::Uint(valid_until.into()),
+        Token::Uint(valid_after.into()),
+    ])))
+}
+
+/// Checks that `paymaster_input` sponsors `request` on behalf of `sponsor` at `timestamp`,
+/// returning the decoded sponsorship.
+pub fn verify_paymaster_input(
+    request: &Eip712TransactionRequest,
+    paymaster: Address,
+    paymaster_input: &[u8],
+    sponsor: Address,
+    timestamp: u64,
+) -> Result<Sponsorship, VerifyingPaymasterError> {
+    let sponsorship = Sponsorship::decode(paymaster_input)?;
+    let digest = sponsorship_digest(
+        request,
+        paymaster,
+        sponsorship.valid_until,
+        sponsorship.valid_after,
+    );
+    let recovered = sponsorship.signature.recover(hash_message(digest))?;
+    if recovered != sponsor {
+        return Err(VerifyingPaymasterError::UnexpectedSigner {
+            expected: sponsor,
+            recovered,
+        });
+    }
+    if timestamp < sponsorship.valid_after || timestamp > sponsorship.valid_until {
+        return Err(VerifyingPaymasterError::OutsideValidityWindow {
+            valid_after: sponsorship.valid_after,
+            valid_until: sponsorship.valid_until,
+            timestamp,
+        });
+    }
+    Ok(sponsorship)
+}
+
+/// Signs the sponsorships of a verifying paymaster, which pays for the transactions its
+/// sponsor approved through the `general` paymaster flow.
+#[derive(Clone, Debug)]
+pub struct VerifyingPaymasterSigner<S> {
+    pub paymaster: Address,
+    pub sponsor: S,
+}
+
+impl<S> VerifyingPaymasterSigner<S>
+where
+    S: Signer,
+{
+    pub fn new(paymaster: Address, sponsor: S) -> Self {
+        Self { paymaster, sponsor }
+    }
+
+    /// Sponsors `request` from `valid_after` to `valid_until` (unix timestamps, at most
+    /// 2^48 - 1), returning the input to send to the paymaster.
+    pub async fn sign(
+        &self,
+        request: &Eip712TransactionRequest,
+        valid_until: u64,
+        valid_after: u64,
+    ) -> Result<Bytes, VerifyingPaymasterError> {
+        check_validity_bounds(valid_until, valid_after)?;
+        let digest = sponsorship_digest(request, self.paymaster, valid_until, valid_after);
+        let signature = self
+            .sponsor
+            .sign_message(digest)
+            .await
+            .map_err(|e| VerifyingPaymasterError::SignerError(e.to_string()))?;
+        Sponsorship {
+            valid_until,
+            valid_after,
+            signature,
+        }
+        .paymaster_input()
+    }
+
+    pub async fn paymaster_params(
+        &self,
+        request: &Eip712TransactionRequest,
+        valid_until: u64,
+        valid_after: u64,
+    ) -> Result<PaymasterParams, VerifyingPaymasterError> {
+        Ok(PaymasterParams::default()
+            .paymaster(self.paymaster)
+            .paymaster_input(self.sign(request, valid_until, valid_after).await?))
+    }
+}
@@ -1,15 +1,9 @@
 use async_trait::async_trait;
 use ethers::{
     abi::{HumanReadableParser, Token, Tokenize},
-    prelude::{
-        k256::{
-            ecdsa::{RecoveryId, Signature as RecoverableSignature},
-            schnorr::signature::hazmat::PrehashSigner,
-        },
-        SignerMiddleware,
-    },
+    prelude::SignerMiddleware,
     providers::{JsonRpcClient, Middleware, Provider, ProviderError},
-    signers::{Signer, Wallet},
+    signers::Signer,
     types::{
         transaction::{eip2718::TypedTransaction, eip712::Eip712Error},
         Address, BlockNumber, Eip1559TransactionRequest, Signature, TransactionReceipt, TxHash,
@@ -26,14 +20,14 @@ pub mod types;
 use types::Fee;
 
 use crate::{
-    eip712::{Eip712Meta, Eip712Transaction, Eip712TransactionRequest},
+    eip712::{Eip712Transaction, Eip712TransactionRequest, Eip712TransactionSigner},
     zks_utils::{self, DEFAULT_GAS, EIP712_TX_TYPE, MAX_FEE_PER_GAS, MAX_PRIORITY_FEE_PER_GAS},
     zks_wallet::{CallRequest, Overrides},
 };
 
 use self::types::{
-    BlockDetails, BlockRange, BridgeContracts, DebugTrace, L1BatchDetails, Proof, TokenInfo,
-    TracerConfig, Transaction, TransactionDetails,
+    BlockDetails, BlockRange, BlockTraceResult, BridgeContracts, DebugTrace, L1BatchDetails, Proof,
+    TokenInfo, TracerConfig, Transaction, TransactionDetails, ZksTransactionReceipt,
 };
 
 /// This trait wraps every JSON-RPC call specified in zkSync Era's documentation
@@ -148,25 +142,33 @@ pub trait ZKSProvider {
         hash: H256,
     ) -> Result<Option<TransactionDetails>, ProviderError>;
 
+    /// Returns the receipt of a transaction along with its L1 batch and L2 to L1 logs.
+    async fn get_zks_transaction_receipt(
+        &self,
+        hash: H256,
+    ) -> Result<Option<ZksTransactionReceipt>, ProviderError>;
+
     /// Returns the latest L1 batch number.
     async fn get_l1_batch_number(&self) -> Result<U256, ProviderError>;
 
     /// Returns the chain id of the underlying L1.
     async fn get_l1_chain_id(&self) -> Result<U256, ProviderError>;
 
-    /// Returns debug trace of all executed calls contained in a block given by its L2 hash.
+    /// Returns the debug traces of all the transactions contained in a block given by its L2 hash,
+    /// one per transaction.
     async fn debug_trace_block_by_hash(
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<DebugTrace, ProviderError>;
+    ) -> Result<Vec<DebugTrace>, ProviderError>;
 
-    /// Returns debug trace of all executed calls contained in a block given by its L2 block number.
+    /// Returns the debug traces of all the transactions contained in a block given by its L2 block
+    /// number, one per transaction.
     async fn debug_trace_block_by_number<T>(
         &self,
         block: T,
         options: Option<TracerConfig>,
-    ) -> Result<DebugTrace, ProviderError>
+    ) -> Result<Vec<DebugTrace>, ProviderError>
     where
         T: Into<U64> + Send + Sync + Serialize + Debug;
 
@@ -188,27 +190,27 @@ pub trait ZKSProvider {
         options: Option<TracerConfig>,
     ) -> Result<DebugTrace, ProviderError>;
 
-    async fn send_eip712<D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send_eip712<'a, W>(
+        &'a self,
+        wallet: &W,
         contract_address: Address,
         function_signature: &str,
         function_parameters: Option<Vec<String>>,
         overrides: Option<Overrides>,
-    ) -> Result<PendingTransaction<Self::ZKProvider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::ZKProvider>, ProviderError>
     where
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync;
+        W: Eip712TransactionSigner;
 
-    async fn send<D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send<'a, W>(
+        &'a self,
+        wallet: &W,
         contract_address: Address,
         function_signature: &str,
         function_parameters: Option<Vec<String>>,
         overrides: Option<Overrides>,
-    ) -> Result<PendingTransaction<Self::Provider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::Provider>, ProviderError>
     where
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync;
+        W: Signer;
 
     async fn wait_for_finalize(
         &self,
@@ -219,14 +221,14 @@ pub trait ZKSProvider {
 
     async fn call(&self, request: &CallRequest) -> Result<Vec<Token>, ProviderError>;
 
-    async fn send_transaction_eip712<T, D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send_transaction_eip712<'a, T, W>(
+        &'a self,
+        wallet: &W,
         transaction: T,
-    ) -> Result<PendingTransaction<Self::ZKProvider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::ZKProvider>, ProviderError>
     where
         T: TryInto<Eip712TransactionRequest> + Send + Sync + Debug,
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync;
+        W: Eip712TransactionSigner;
 }
 
 #[async_trait]
@@ -353,6 +355,13 @@ impl<M: Middleware + ZKSProvider, S: Signer> ZKSProvider for SignerMiddleware<M,
         self.inner().get_transaction_details(hash).await
     }
 
+    async fn get_zks_transaction_receipt(
+        &self,
+        hash: H256,
+    ) -> Result<Option<ZksTransactionReceipt>, ProviderError> {
+        self.inner().get_zks_transaction_receipt(hash).await
+    }
+
     async fn get_l1_batch_number(&self) -> Result<U256, ProviderError> {
         self.inner().get_l1_batch_number().await
     }
@@ -365,7 +374,7 @@ impl<M: Middleware + ZKSProvider, S: Signer> ZKSProvider for SignerMiddleware<M,
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<DebugTrace, ProviderError> {
+    ) -> Result<Vec<DebugTrace>, ProviderError> {
         ZKSProvider::debug_trace_block_by_hash(self.inner(), hash, options).await
     }
 
@@ -373,7 +382,7 @@ impl<M: Middleware + ZKSProvider, S: Signer> ZKSProvider for SignerMiddleware<M,
         &self,
         block: T,
         options: Option<TracerConfig>,
-    ) -> Result<DebugTrace, ProviderError>
+    ) -> Result<Vec<DebugTrace>, ProviderError>
     where
         T: Into<U64> + Send + Sync + Serialize + Debug,
     {
@@ -401,16 +410,16 @@ impl<M: Middleware + ZKSProvider, S: Signer> ZKSProvider for SignerMiddleware<M,
         ZKSProvider::debug_trace_transaction(self.inner(), hash, options).await
     }
 
-    async fn send_eip712<D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send_eip712<'a, W>(
+        &'a self,
+        wallet: &W,
         contract_address: Address,
         function_signature: &str,
         function_parameters: Option<Vec<String>>,
         overrides: Option<Overrides>,
-    ) -> Result<PendingTransaction<Self::ZKProvider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::ZKProvider>, ProviderError>
     where
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+        W: Eip712TransactionSigner,
     {
         self.inner()
             .send_eip712(
@@ -423,16 +432,16 @@ impl<M: Middleware + ZKSProvider, S: Signer> ZKSProvider for SignerMiddleware<M,
             .await
     }
 
-    async fn send<D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send<'a, W>(
+        &'a self,
+        wallet: &W,
         contract_address: Address,
         function_signature: &str,
         function_parameters: Option<Vec<String>>,
         _overrides: Option<Overrides>,
-    ) -> Result<PendingTransaction<Self::Provider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::Provider>, ProviderError>
     where
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+        W: Signer,
     {
         let tx = build_send_tx(
             self,
@@ -448,14 +457,14 @@ impl<M: Middleware + ZKSProvider, S: Signer> ZKSProvider for SignerMiddleware<M,
             .map_err(|e| ProviderError::CustomError(format!("Error sending transaction: {e:?}")))
     }
 
-    async fn send_transaction_eip712<T, D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send_transaction_eip712<'a, T, W>(
+        &'a self,
+        wallet: &W,
         transaction: T,
-    ) -> Result<PendingTransaction<Self::ZKProvider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::ZKProvider>, ProviderError>
     where
         T: TryInto<Eip712TransactionRequest> + Sync + Send + Debug,
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+        W: Eip712TransactionSigner,
     {
         self.inner()
             .send_transaction_eip712(wallet, transaction)
@@ -611,6 +620,13 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
         self.request("zks_getTransactionDetails", [hash]).await
     }
 
+    async fn get_zks_transaction_receipt(
+        &self,
+        hash: H256,
+    ) -> Result<Option<ZksTransactionReceipt>, ProviderError> {
+        self.request("eth_getTransactionReceipt", [hash]).await
+    }
+
     async fn get_l1_batch_number(&self) -> Result<U256, ProviderError> {
         self.request("zks_L1BatchNumber", ()).await
     }
@@ -623,49 +639,25 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<DebugTrace, ProviderError> {
-        let processable_response = self
-            .request::<serde_json::Value, serde_json::Value>(
-                "debug_traceBlockByHash",
-                json!([hash, options]),
-            )
-            .await?
-            .get(0)
-            .ok_or(ProviderError::CustomError(
-                "error on debug_trace_block_by_hash".to_owned(),
-            ))?
-            .get("result")
-            .ok_or(ProviderError::CustomError(
-                "error on debug_trace_block_by_hash".to_owned(),
-            ))?
-            .clone();
-        serde_json::from_value(processable_response).map_err(ProviderError::SerdeJson)
+    ) -> Result<Vec<DebugTrace>, ProviderError> {
+        let traces: Vec<BlockTraceResult> = self
+            .request("debug_traceBlockByHash", json!([hash, options]))
+            .await?;
+        Ok(traces.into_iter().map(|trace| trace.result).collect())
     }
 
     async fn debug_trace_block_by_number<T>(
         &self,
         block: T,
         options: Option<TracerConfig>,
-    ) -> Result<DebugTrace, ProviderError>
+    ) -> Result<Vec<DebugTrace>, ProviderError>
     where
         T: Into<U64> + Send + Sync + Serialize + Debug,
     {
-        let processable_response = self
-            .request::<serde_json::Value, serde_json::Value>(
-                "debug_traceBlockByNumber",
-                json!([block, options]),
-            )
-            .await?
-            .get(0)
-            .ok_or(ProviderError::CustomError(
-                "error on debug_trace_block_by_hash".to_owned(),
-            ))?
-            .get("result")
-            .ok_or(ProviderError::CustomError(
-                "error on debug_trace_block_by_hash".to_owned(),
-            ))?
-            .clone();
-        serde_json::from_value(processable_response).map_err(ProviderError::SerdeJson)
+        let traces: Vec<BlockTraceResult> = self
+            .request("debug_traceBlockByNumber", json!([block.into(), options]))
+            .await?;
+        Ok(traces.into_iter().map(|trace| trace.result).collect())
     }
 
     async fn debug_trace_call<R, T>(
@@ -691,14 +683,14 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
             .await
     }
 
-    async fn send_transaction_eip712<T, D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send_transaction_eip712<'a, T, W>(
+        &'a self,
+        wallet: &W,
         transaction: T,
-    ) -> Result<PendingTransaction<Self::ZKProvider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::ZKProvider>, ProviderError>
     where
         T: TryInto<Eip712TransactionRequest> + Sync + Send + Debug,
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+        W: Eip712TransactionSigner,
     {
         let mut request: Eip712TransactionRequest = transaction.try_into().map_err(|_e| {
             ProviderError::CustomError("error on send_transaction_eip712".to_owned())
@@ -722,7 +714,7 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
             .try_into()
             .map_err(|e: Eip712Error| ProviderError::CustomError(e.to_string()))?;
         let signature: Signature = wallet
-            .sign_typed_data(&signable_data)
+            .sign_transaction_eip712(&signable_data)
             .await
             .map_err(|e| ProviderError::CustomError(format!("error signing transaction: {e}")))?;
         request = request.custom_data(custom_data.custom_signature(signature.to_vec()));
@@ -734,16 +726,16 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
             .await
     }
 
-    async fn send_eip712<D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send_eip712<'a, W>(
+        &'a self,
+        wallet: &W,
         contract_address: Address,
         function_signature: &str,
         function_parameters: Option<Vec<String>>,
         overrides: Option<Overrides>,
-    ) -> Result<PendingTransaction<Self::ZKProvider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::ZKProvider>, ProviderError>
     where
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+        W: Eip712TransactionSigner,
     {
         // Note: We couldn't implement ProviderError::LexerError because ethers-rs's LexerError is not exposed.
         // TODO check for ECADD precompile address to get the function signature.
@@ -794,11 +786,14 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
             .try_into()
             .map_err(|e: Eip712Error| ProviderError::CustomError(e.to_string()))?;
         let signature: Signature = wallet
-            .sign_typed_data(&signable_data)
+            .sign_transaction_eip712(&signable_data)
             .await
             .map_err(|e| ProviderError::CustomError(format!("error signing transaction: {e}")))?;
-        send_request =
-            send_request.custom_data(Eip712Meta::new().custom_signature(signature.to_vec()));
+        let custom_data = send_request
+            .custom_data
+            .clone()
+            .custom_signature(signature.to_vec());
+        send_request = send_request.custom_data(custom_data);
 
         let encoded_rlp = &*send_request
             .rlp_signed(signature)
@@ -807,16 +802,16 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
             .await
     }
 
-    async fn send<D>(
-        &self,
-        wallet: &Wallet<D>,
+    async fn send<'a, W>(
+        &'a self,
+        wallet: &W,
         contract_address: Address,
         function_signature: &str,
         function_parameters: Option<Vec<String>>,
         _overrides: Option<Overrides>,
-    ) -> Result<PendingTransaction<Self::Provider>, ProviderError>
+    ) -> Result<PendingTransaction<'a, Self::Provider>, ProviderError>
     where
-        D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+        W: Signer,
     {
         let tx = build_send_tx(
             self,
@@ -896,16 +891,16 @@ impl<P: JsonRpcClient> ZKSProvider for Provider<P> {
     }
 }
 
-async fn build_send_tx<D>(
+async fn build_send_tx<W>(
     provider: &impl Middleware,
-    wallet: &Wallet<D>,
+    wallet: &W,
     contract_address: Address,
     function_signature: &str,
     function_parameters: Option<Vec<String>>,
     _overrides: Option<Overrides>,
 ) -> Result<TypedTransaction, ProviderError>
 where
-    D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Send + Sync,
+    W: Signer,
 {
     let function = HumanReadableParser::parse_function(function_signature)
         .map_err(|e| ProviderError::CustomError(e.to_string()))?;
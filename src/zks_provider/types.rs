@@ -1,4 +1,4 @@
-use ethers::types::{Address, Bytes, H256, U256, U64};
+use ethers::types::{Address, Bytes, TransactionReceipt, H256, U256, U64};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -97,18 +97,21 @@ pub struct Proof {
     pub root: Bytes,
 }
 
-// TODO: Complete struct.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Transaction {
     pub common_data: CommonData,
     pub execute: Execute,
     pub received_timestamp_ms: u64,
+    #[serde(default)]
+    pub raw_bytes: Option<Bytes>,
 }
 
+/// Data specific to the origin of a [`Transaction`].
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CommonData {
-    #[serde(rename = "L1")]
-    pub l1: L1,
+pub enum CommonData {
+    L1(L1),
+    L2(L2),
+    ProtocolUpgrade(ProtocolUpgrade),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -131,12 +134,70 @@ pub struct L1 {
     pub to_mint: U256,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct L2 {
+    pub nonce: u32,
+    pub fee: Fee,
+    pub initiator_address: Address,
+    pub signature: Vec<u8>,
+    pub transaction_type: TransactionType,
+    pub input: Option<InputData>,
+    pub paymaster_params: PaymasterParams,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgrade {
+    pub sender: Address,
+    pub upgrade_id: u16,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    pub gas_per_pubdata_limit: U256,
+    pub eth_hash: H256,
+    pub eth_block: u64,
+    pub canonical_tx_hash: H256,
+    pub to_mint: U256,
+    pub refund_recipient: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionType {
+    #[serde(rename = "LegacyTransaction")]
+    Legacy,
+    #[serde(rename = "EIP2930Transaction")]
+    Eip2930,
+    #[serde(rename = "EIP1559Transaction")]
+    Eip1559,
+    #[serde(rename = "EIP712Transaction")]
+    Eip712,
+    #[serde(rename = "PriorityOpTransaction")]
+    PriorityOp,
+    #[serde(rename = "ProtocolUpgradeTransaction")]
+    ProtocolUpgrade,
+}
+
+/// The raw signed transaction and its hash, as received by the node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InputData {
+    pub hash: H256,
+    pub data: Vec<u8>,
+}
+
+/// Paymaster of an L2 transaction. The address is zero when no paymaster is used.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymasterParams {
+    pub paymaster: Address,
+    pub paymaster_input: Vec<u8>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Execute {
     pub calldata: Bytes,
     pub contract_address: Address,
-    pub factory_deps: Vec<Vec<u8>>,
+    pub factory_deps: Option<Vec<Vec<u8>>>,
     pub value: U256,
 }
 
@@ -170,19 +231,174 @@ pub struct TracerConfig {
     pub tracer_config: Option<HashMap<String, bool>>,
 }
 
-// TODO: Check correct types for the ones using serde_json::Value.
+/// Kind of call recorded in a [`CallFrame`].
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CallType {
+    Call,
+    StaticCall,
+    DelegateCall,
+    CallCode,
+    Create,
+    Create2,
+    SelfDestruct,
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for CallType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // zkSync nodes and geth do not agree on the casing of the call type.
+        let call_type = String::deserialize(deserializer)?;
+        Ok(match call_type.to_uppercase().as_str() {
+            "CALL" => CallType::Call,
+            "STATICCALL" => CallType::StaticCall,
+            "DELEGATECALL" => CallType::DelegateCall,
+            "CALLCODE" => CallType::CallCode,
+            "CREATE" => CallType::Create,
+            "CREATE2" => CallType::Create2,
+            "SELFDESTRUCT" => CallType::SelfDestruct,
+            _ => CallType::Unknown,
+        })
+    }
+}
+
+/// A single call of a `callTracer` debug trace, with its nested sub-calls.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub call_type: CallType,
+    pub from: Address,
+    /// Missing on some frames, e.g. failed contract creations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    #[serde(default)]
+    pub value: U256,
+    #[serde(default)]
+    pub gas: U256,
+    #[serde(default)]
+    pub gas_used: U256,
+    #[serde(default)]
+    pub input: Bytes,
+    #[serde(default)]
+    pub output: Bytes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+}
+
+pub type DebugTrace = CallFrame;
+
+impl CallFrame {
+    /// Visits this frame and all of its sub-calls depth first, passing each frame along
+    /// with its depth in the tree (the root has depth 0).
+    pub fn walk<F>(&self, mut visit: F)
+    where
+        F: FnMut(&CallFrame, usize),
+    {
+        self.walk_at_depth(&mut visit, 0);
+    }
+
+    fn walk_at_depth<F>(&self, visit: &mut F, depth: usize)
+    where
+        F: FnMut(&CallFrame, usize),
+    {
+        visit(self, depth);
+        for call in &self.calls {
+            call.walk_at_depth(visit, depth + 1);
+        }
+    }
+
+    /// Returns this frame and all of its sub-calls in depth first order.
+    pub fn flatten(&self) -> Vec<&CallFrame> {
+        self.filter(|_| true)
+    }
+
+    /// Returns the frames of the tree, in depth first order, for which `predicate` holds.
+    pub fn filter<P>(&self, mut predicate: P) -> Vec<&CallFrame>
+    where
+        P: FnMut(&CallFrame) -> bool,
+    {
+        let mut frames = Vec::new();
+        self.collect_frames(&mut predicate, &mut frames);
+        frames
+    }
+
+    fn collect_frames<'a, P>(&'a self, predicate: &mut P, frames: &mut Vec<&'a CallFrame>)
+    where
+        P: FnMut(&CallFrame) -> bool,
+    {
+        if predicate(self) {
+            frames.push(self);
+        }
+        for call in &self.calls {
+            call.collect_frames(predicate, frames);
+        }
+    }
+
+    /// Returns the 4-byte function selector of the call input, if any.
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.input.get(..4)?.try_into().ok()
+    }
+
+    pub fn is_reverted(&self) -> bool {
+        self.error.is_some() || self.revert_reason.is_some()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct BlockTraceResult {
+    pub result: CallFrame,
+}
+
+/// A message or service log sent from L2 to L1, as found in zkSync transaction receipts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct L2ToL1Log {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_batch_number: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_index: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_log_index: Option<U256>,
+    pub shard_id: U64,
+    pub is_service: bool,
+    /// Index of the transaction in its L1 batch, i.e. the `_l2TxNumberInBlock` expected by
+    /// the L1 contracts.
+    #[serde(
+        rename = "txIndexInL1Batch",
+        alias = "txNumberInBlock",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tx_number_in_block: Option<U64>,
+    pub sender: Address,
+    pub key: H256,
+    pub value: H256,
+    pub log_index: U256,
+}
+
+/// A transaction receipt along with the zkSync specific fields.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
-pub struct DebugTrace {
-    calls: Vec<serde_json::Value>,
-    error: Option<String>,
-    from: Address,
-    gas: U256,
-    gas_used: U256,
-    input: Bytes,
-    output: Bytes,
-    revert_reason: Option<String>,
-    to: Address,
-    r#type: String,
-    value: U256,
+pub struct ZksTransactionReceipt {
+    #[serde(flatten)]
+    pub receipt: TransactionReceipt,
+    /// `None` until the transaction is included in an L1 batch.
+    pub l1_batch_number: Option<U64>,
+    pub l1_batch_tx_index: Option<U64>,
+    #[serde(default)]
+    pub l2_to_l1_logs: Vec<L2ToL1Log>,
 }
@@ -4,7 +4,7 @@ use ethers::{
         token::{LenientTokenizer, StrictTokenizer, Tokenizer},
         Constructor, Function, Param, ParamType, Token,
     },
-    types::{Address, H160, U256},
+    types::{Address, BigEndianHash, H160, H256, U256},
 };
 use ethers_contract::AbiError;
 use std::str::FromStr;
@@ -38,6 +38,7 @@ pub const RECOMMENDED_DEPOSIT_L1_GAS_LIMIT: u64 = 10000000;
 pub const RECOMMENDED_DEPOSIT_L2_GAS_LIMIT: u64 = 10000000;
 pub const DEPOSIT_GAS_PER_PUBDATA_LIMIT: u64 = 800;
 pub const DEFAULT_ERC20_DEPOSIT_GAS_LIMIT: u64 = 300000_u64;
+pub const DEFAULT_ERC20_DEPOSIT_L2_GAS_LIMIT: u64 = 3000000_u64;
 
 /* Contracts */
 
@@ -74,6 +75,43 @@ pub const ETHER_L1_ADDRESS: Address = H160([
     0x00, 0x00, 0x00, 0x00,
 ]);
 
+/// Address of the L2EthToken system contract, which holds the ETH balances on L2.
+pub const L2_ETH_TOKEN_ADDRESS: Address = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x0a,
+]);
+
+pub const BOOTLOADER_ADDRESS: Address = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x01,
+]);
+
+/// Added to the address of L1 contracts that send L1->L2 transactions, so that they can't be
+/// impersonated by L2 accounts with the same address.
+pub const L1_TO_L2_ALIAS_OFFSET: Address = H160([
+    0x11, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x11, 0x11,
+]);
+
+/// Returns the address with which the L1 contract `address` sends L1->L2 transactions.
+pub fn apply_l1_to_l2_alias(address: Address) -> Address {
+    let address = U256::from_big_endian(address.as_bytes());
+    let offset = U256::from_big_endian(L1_TO_L2_ALIAS_OFFSET.as_bytes());
+    Address::from(H256::from_uint(&address.overflowing_add(offset).0))
+}
+
+/// Returns the L1 contract that sends L1->L2 transactions from the aliased `address`.
+pub fn undo_l1_to_l2_alias(address: Address) -> Address {
+    let address = U256::from_big_endian(address.as_bytes());
+    let offset = U256::from_big_endian(L1_TO_L2_ALIAS_OFFSET.as_bytes());
+    Address::from(H256::from_uint(&address.overflowing_sub(offset).0))
+}
+
+/// ETH is referred to either by the zero address or by the L2EthToken system contract.
+pub fn is_eth(token: Address) -> bool {
+    token == ETHER_L1_ADDRESS || token == L2_ETH_TOKEN_ADDRESS
+}
+
 /* Precompiles */
 
 pub const ECRECOVER_PRECOMPILE_ADDRESS: Address = H160([
@@ -0,0 +1,77 @@
+use ethers::{
+    providers::{Middleware, ProviderError},
+    types::{TransactionReceipt, H256},
+};
+use std::{sync::Arc, time::Duration};
+use tokio::time::Instant;
+
+const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// An L1 deposit, or any L1 to L2 request, and the L2 transaction it requested, as returned by
+/// [`ZKSWallet::deposit`](super::ZKSWallet::deposit) and
+/// [`ZKSWallet::request_execute`](super::ZKSWallet::request_execute).
+#[derive(Debug, Clone)]
+pub struct DepositHandle<M> {
+    /// Hash of the transaction on L1.
+    pub l1_tx_hash: H256,
+    /// Hash of the L2 transaction, taken from the `NewPriorityRequest` event.
+    pub l2_tx_hash: H256,
+    era_provider: Arc<M>,
+    polling_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl<M> DepositHandle<M>
+where
+    M: Middleware,
+{
+    pub(crate) fn new(l1_tx_hash: H256, l2_tx_hash: H256, era_provider: Arc<M>) -> Self {
+        Self {
+            l1_tx_hash,
+            l2_tx_hash,
+            era_provider,
+            polling_interval: DEFAULT_POLLING_INTERVAL,
+            timeout: Some(DEFAULT_TIMEOUT),
+        }
+    }
+
+    pub fn polling_interval(mut self, polling_interval: Duration) -> Self {
+        self.polling_interval = polling_interval;
+        self
+    }
+
+    /// Sets how long [`Self::l2_receipt`] waits, `None` waits forever.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Waits until the request is executed on L2 and returns its L2 receipt.
+    pub async fn l2_receipt(&self) -> Result<TransactionReceipt, ProviderError> {
+        let mut timer = tokio::time::interval(self.polling_interval);
+        let start = Instant::now();
+
+        loop {
+            timer.tick().await;
+
+            if let Some(receipt) = self
+                .era_provider
+                .get_transaction_receipt(self.l2_tx_hash)
+                .await
+                .map_err(|e| ProviderError::CustomError(e.to_string()))?
+            {
+                return Ok(receipt);
+            }
+
+            if let Some(timeout) = self.timeout {
+                if start.elapsed() >= timeout {
+                    return Err(ProviderError::CustomError(format!(
+                        "Timed out waiting for the L2 transaction {:?}",
+                        self.l2_tx_hash
+                    )));
+                }
+            }
+        }
+    }
+}
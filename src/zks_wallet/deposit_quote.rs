@@ -0,0 +1,41 @@
+use ethers::types::{Eip1559TransactionRequest, U256};
+
+/// The cost breakdown of a deposit, as computed by
+/// [`ZKSWallet::quote_deposit`](super::ZKSWallet::quote_deposit).
+#[derive(Clone, Debug)]
+pub struct DepositQuote {
+    /// L1 gas limit of the deposit transaction.
+    pub gas_limit: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub l2_gas_limit: U256,
+    /// `l2TransactionBaseCost` of the L2 execution, paid in ETH on L1.
+    pub base_cost: U256,
+    pub operator_tip: U256,
+    /// Approval of the bridge, sent before the deposit when its ERC-20 allowance is too low.
+    pub approval: Option<Eip1559TransactionRequest>,
+    /// The deposit transaction, ready to be sent.
+    pub transaction: Eip1559TransactionRequest,
+}
+
+impl DepositQuote {
+    /// ETH sent along with the deposit transaction: the base cost, the operator tip and, for ETH
+    /// deposits, the amount.
+    pub fn l1_value(&self) -> U256 {
+        self.transaction.value.unwrap_or_default()
+    }
+
+    /// Upper bound of the ETH spent on L1, approval included. Saturates at `U256::MAX`, which
+    /// remains an upper bound.
+    pub fn max_total_cost(&self) -> U256 {
+        let approval_gas = self
+            .approval
+            .as_ref()
+            .and_then(|approval| approval.gas)
+            .unwrap_or_default();
+        self.gas_limit
+            .saturating_add(approval_gas)
+            .saturating_mul(self.max_fee_per_gas)
+            .saturating_add(self.l1_value())
+    }
+}
@@ -1,32 +1,31 @@
 use ethers::{
     abi::{Error, ParseError},
-    prelude::{
-        k256::{
-            ecdsa::{RecoveryId, Signature as RecoverableSignature},
-            schnorr::signature::hazmat::PrehashSigner,
-        },
-        signer::SignerMiddlewareError,
-        AbiError, ContractError, SignerMiddleware,
-    },
+    prelude::{signer::SignerMiddlewareError, AbiError, ContractError, SignerMiddleware},
     providers::{Middleware, ProviderError},
-    signers::{Wallet, WalletError},
-    types::transaction::eip712::Eip712Error,
+    signers::{Signer, WalletError},
+    types::{transaction::eip712::Eip712Error, Address, H256, U256},
 };
 
+use super::AccountSignatureProvider;
 use crate::contracts::main_contract::MainContractError;
 
 #[derive(thiserror::Error, Debug)]
-pub enum ZKSWalletError<M, D>
+pub enum ZKSWalletError<M, L1, L2 = L1>
 where
     M: Middleware,
-    D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Sync + Send,
+    L1: Signer,
+    L2: Signer,
 {
     #[error("Provider error: {0}")]
     ProviderError(#[from] ProviderError),
     #[error("Middleware error: {0}")]
-    MiddlewareError(#[from] SignerMiddlewareError<M, Wallet<D>>),
+    MiddlewareError(#[from] SignerMiddlewareError<M, L1>),
+    #[error("L2 middleware error: {0}")]
+    L2MiddlewareError(SignerMiddlewareError<M, L2>),
     #[error("Wallet error: {0}")]
     EthWalletError(#[from] WalletError),
+    #[error("Signer error: {0}")]
+    SignerError(L2::Error),
     #[error("ABI error: {0}")]
     AbiError(#[from] AbiError),
     #[error("EIP712 error: {0}")]
@@ -41,20 +40,47 @@ where
     RequestConversionError(#[from] ZKRequestError),
     #[error("{0}")]
     CustomError(String),
+    #[error("Deposit succeeded on L2 in transaction {0:?}, there is nothing to claim")]
+    DepositSucceeded(H256),
     #[error("Main contract error: {0}")]
-    MainContractError(#[from] MainContractError<M, D>),
+    MainContractError(#[from] MainContractError<M, L1>),
+    #[error("Converting a fee of {fee} wei into token {token:?} overflows")]
+    FeeConversionOverflow { token: Address, fee: U256 },
+    #[error("Token {token:?} has {decimals} decimals, at most 77 are supported")]
+    UnsupportedTokenDecimals { token: Address, decimals: U256 },
 }
 
-impl<M, D> From<ContractError<SignerMiddleware<M, Wallet<D>>>> for ZKSWalletError<M, D>
+impl<M, L1, L2> From<ContractError<SignerMiddleware<M, L1>>> for ZKSWalletError<M, L1, L2>
 where
     M: Middleware,
-    D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Sync + Send,
+    L1: Signer,
+    L2: Signer,
 {
-    fn from(value: ContractError<SignerMiddleware<M, Wallet<D>>>) -> Self {
+    fn from(value: ContractError<SignerMiddleware<M, L1>>) -> Self {
         Self::CustomError(format!("{value:?}"))
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum SmartAccountError<M, P>
+where
+    M: Middleware,
+    P: AccountSignatureProvider,
+{
+    #[error("Provider error: {0}")]
+    ProviderError(#[from] ProviderError),
+    #[error("Middleware error: {0}")]
+    MiddlewareError(M::Error),
+    #[error("EIP712 error: {0}")]
+    Eip712Error(#[from] Eip712Error),
+    #[error("Account signature error: {0}")]
+    SignatureProviderError(P::Error),
+    #[error("Request conversion error: {0}")]
+    RequestConversionError(#[from] ZKRequestError),
+    #[error("{0}")]
+    CustomError(String),
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ZKRequestError {
     #[error("Error parsing function: {0}")]
@@ -1,16 +1,35 @@
 mod errors;
-pub use errors::{ZKRequestError, ZKSWalletError};
+pub use errors::{SmartAccountError, ZKRequestError, ZKSWalletError};
 
 mod requests;
 pub use requests::{
-    call_request::CallRequest, deploy_request::DeployRequest, deposit_request::DepositRequest,
-    transfer_request::TransferRequest, withdraw_request::WithdrawRequest,
+    call_request::CallRequest,
+    deploy_request::{AccountAbstractionVersion, DeployRequest},
+    deposit_request::DepositRequest,
+    execute_request::ExecuteRequest,
+    transfer_request::TransferRequest,
+    withdraw_request::WithdrawRequest,
 };
 
+mod deposit_handle;
+pub use deposit_handle::DepositHandle;
+
+mod deposit_quote;
+pub use deposit_quote::DepositQuote;
+
+mod paymaster;
+pub use paymaster::TokenRateOracle;
+
+mod smart_account;
+pub use smart_account::{AccountSignatureProvider, MultisigSignatureProvider, SmartAccount};
+
 mod wallet;
-pub use wallet::ZKSWallet;
+pub use wallet::{WithdrawalStatus, ZKSWallet};
 
+use crate::eip712::PaymasterParams;
 use ethers::types::U256;
+#[derive(Default)]
 pub struct Overrides {
     pub value: Option<U256>,
+    pub paymaster_params: Option<PaymasterParams>,
 }
@@ -0,0 +1,14 @@
+use ethers::{
+    providers::ProviderError,
+    types::{Address, U256},
+};
+use std::fmt::Debug;
+
+/// Converts transaction fees into the ERC-20 tokens taken by approval-based paymasters.
+///
+/// Wallets without an oracle use the USD prices returned by `zks_getTokenPrice`.
+#[async_trait::async_trait]
+pub trait TokenRateOracle: Debug + Send + Sync {
+    /// Returns the amount of `token`, in its smallest unit, worth `fee` wei.
+    async fn fee_in_token(&self, token: Address, fee: U256) -> Result<U256, ProviderError>;
+}
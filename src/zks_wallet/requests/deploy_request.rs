@@ -1,6 +1,28 @@
-use ethers::{abi::Abi, types::Address};
+use crate::eip712::PaymasterParams;
+use ethers::{
+    abi::Abi,
+    types::{Address, H256},
+};
 use std::fmt::Debug;
 
+/// Version of the account abstraction protocol supported by a deployed contract. Contracts
+/// deployed with a version other than `None` are accounts and can initiate transactions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AccountAbstractionVersion {
+    #[default]
+    None,
+    Version1,
+}
+
+impl From<AccountAbstractionVersion> for u8 {
+    fn from(version: AccountAbstractionVersion) -> Self {
+        match version {
+            AccountAbstractionVersion::None => 0,
+            AccountAbstractionVersion::Version1 => 1,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DeployRequest {
     pub contract_abi: Abi,
@@ -8,6 +30,10 @@ pub struct DeployRequest {
     pub constructor_parameters: Vec<String>,
     pub from: Address,
     pub factory_deps: Option<Vec<Vec<u8>>>,
+    /// Deploys with `create2` (or `create2Account`) when set.
+    pub salt: Option<H256>,
+    pub account_abstraction_version: AccountAbstractionVersion,
+    pub paymaster_params: Option<PaymasterParams>,
 }
 
 impl DeployRequest {
@@ -22,6 +48,9 @@ impl DeployRequest {
             constructor_parameters,
             from: Default::default(),
             factory_deps: None,
+            salt: None,
+            account_abstraction_version: Default::default(),
+            paymaster_params: None,
         }
     }
 
@@ -34,4 +63,24 @@ impl DeployRequest {
         self.factory_deps = Some(factory_deps);
         self
     }
+
+    pub fn salt(mut self, salt: H256) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    pub fn account_abstraction_version(
+        mut self,
+        account_abstraction_version: AccountAbstractionVersion,
+    ) -> Self {
+        self.account_abstraction_version = account_abstraction_version;
+        self
+    }
+
+    /// Lets a paymaster pay the fee of the deployment, e.g. so that a fresh account can deploy
+    /// without holding ETH.
+    pub fn paymaster_params(mut self, paymaster_params: PaymasterParams) -> Self {
+        self.paymaster_params = Some(paymaster_params);
+        self
+    }
 }
@@ -1,17 +1,6 @@
 use crate::types::{Address, U256};
 
-use crate::zks_utils::{
-    DEPOSIT_GAS_PER_PUBDATA_LIMIT, ETHER_L1_ADDRESS, RECOMMENDED_DEPOSIT_L1_GAS_LIMIT,
-    RECOMMENDED_DEPOSIT_L2_GAS_LIMIT,
-};
-
-fn default_gas_limit() -> U256 {
-    RECOMMENDED_DEPOSIT_L1_GAS_LIMIT.into()
-}
-
-fn default_l2_gas_limit() -> U256 {
-    RECOMMENDED_DEPOSIT_L2_GAS_LIMIT.into()
-}
+use crate::zks_utils::{DEPOSIT_GAS_PER_PUBDATA_LIMIT, ETHER_L1_ADDRESS};
 
 fn default_gas_per_pubdata_byte() -> U256 {
     DEPOSIT_GAS_PER_PUBDATA_LIMIT.into()
@@ -21,11 +10,22 @@ fn default_gas_per_pubdata_byte() -> U256 {
 pub struct DepositRequest {
     pub amount: U256,
     pub to: Option<Address>,
-    pub l2_gas_limit: U256,
+    /// Estimated with `zks_estimateGasL1ToL2` when `None`. The L2 side of custom bridges is
+    /// unknown, their deposits use `DEFAULT_ERC20_DEPOSIT_L2_GAS_LIMIT` instead.
+    pub l2_gas_limit: Option<U256>,
     pub gas_per_pubdata_byte: U256,
     pub operator_tip: U256,
+    /// Legacy way of pricing the deposit: used as `max_fee_per_gas` when that is `None`, and
+    /// ignored otherwise.
     pub gas_price: Option<U256>,
-    pub gas_limit: U256,
+    /// Filled from `gas_price`, or else from the L1 fee history, when `None`. Also prices the
+    /// base cost.
+    pub max_fee_per_gas: Option<U256>,
+    /// Filled from the L1 fee history when `None`.
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// L1 gas limit, estimated with `eth_estimateGas` when `None`. ERC-20 deposits waiting for
+    /// their approval are quoted with a per-token default and estimated once it is sent.
+    pub gas_limit: Option<U256>,
     pub token: Address,
     pub bridge_address: Option<Address>,
 }
@@ -35,11 +35,13 @@ impl DepositRequest {
         Self {
             amount,
             to: None,
-            l2_gas_limit: default_l2_gas_limit(),
+            l2_gas_limit: None,
             gas_per_pubdata_byte: default_gas_per_pubdata_byte(),
             operator_tip: 0_i32.into(),
             gas_price: None,
-            gas_limit: default_gas_limit(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit: None,
             token: ETHER_L1_ADDRESS,
             bridge_address: None,
         }
@@ -55,10 +57,7 @@ impl DepositRequest {
     }
 
     pub fn l2_gas_limit(mut self, value: Option<U256>) -> Self {
-        self.l2_gas_limit = match value {
-            Some(l2_gas_limit) => l2_gas_limit,
-            None => default_l2_gas_limit(),
-        };
+        self.l2_gas_limit = value;
         self
     }
 
@@ -80,11 +79,18 @@ impl DepositRequest {
         self
     }
 
+    pub fn max_fee_per_gas(mut self, value: Option<U256>) -> Self {
+        self.max_fee_per_gas = value;
+        self
+    }
+
+    pub fn max_priority_fee_per_gas(mut self, value: Option<U256>) -> Self {
+        self.max_priority_fee_per_gas = value;
+        self
+    }
+
     pub fn gas_limit(mut self, value: Option<U256>) -> Self {
-        self.gas_limit = match value {
-            Some(gas_limit) => gas_limit,
-            _ => default_gas_limit(),
-        };
+        self.gas_limit = value;
         self
     }
 
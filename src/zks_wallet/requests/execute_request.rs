@@ -0,0 +1,86 @@
+use crate::zks_utils::DEPOSIT_GAS_PER_PUBDATA_LIMIT;
+use ethers::types::{Address, Bytes, U256};
+
+/// An L2 contract call requested from L1 through the main contract's priority queue.
+#[derive(Clone, Debug)]
+pub struct ExecuteRequest {
+    pub contract_address: Address,
+    pub calldata: Bytes,
+    pub l2_value: U256,
+    pub factory_deps: Vec<Bytes>,
+    /// Receives the refund of the unused L2 gas, the sender when `None`.
+    pub refund_recipient: Option<Address>,
+    /// Estimated with `zks_estimateGasL1ToL2` when `None`.
+    pub l2_gas_limit: Option<U256>,
+    pub gas_per_pubdata_byte: U256,
+    pub operator_tip: U256,
+    /// Filled from the L1 fee history when `None`, also prices the base cost.
+    pub max_fee_per_gas: Option<U256>,
+    /// Filled from the L1 fee history when `None`.
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// L1 gas limit, estimated when `None`.
+    pub gas_limit: Option<U256>,
+}
+
+impl ExecuteRequest {
+    pub fn new(contract_address: Address, calldata: Bytes) -> Self {
+        Self {
+            contract_address,
+            calldata,
+            l2_value: U256::zero(),
+            factory_deps: Vec::new(),
+            refund_recipient: None,
+            l2_gas_limit: None,
+            gas_per_pubdata_byte: DEPOSIT_GAS_PER_PUBDATA_LIMIT.into(),
+            operator_tip: U256::zero(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit: None,
+        }
+    }
+
+    pub fn l2_value(mut self, l2_value: U256) -> Self {
+        self.l2_value = l2_value;
+        self
+    }
+
+    pub fn factory_deps(mut self, factory_deps: Vec<Bytes>) -> Self {
+        self.factory_deps = factory_deps;
+        self
+    }
+
+    pub fn refund_recipient(mut self, refund_recipient: Address) -> Self {
+        self.refund_recipient = Some(refund_recipient);
+        self
+    }
+
+    pub fn l2_gas_limit(mut self, value: Option<U256>) -> Self {
+        self.l2_gas_limit = value;
+        self
+    }
+
+    pub fn gas_per_pubdata_byte(mut self, gas_per_pubdata_byte: U256) -> Self {
+        self.gas_per_pubdata_byte = gas_per_pubdata_byte;
+        self
+    }
+
+    pub fn operator_tip(mut self, operator_tip: U256) -> Self {
+        self.operator_tip = operator_tip;
+        self
+    }
+
+    pub fn max_fee_per_gas(mut self, value: Option<U256>) -> Self {
+        self.max_fee_per_gas = value;
+        self
+    }
+
+    pub fn max_priority_fee_per_gas(mut self, value: Option<U256>) -> Self {
+        self.max_priority_fee_per_gas = value;
+        self
+    }
+
+    pub fn gas_limit(mut self, value: Option<U256>) -> Self {
+        self.gas_limit = value;
+        self
+    }
+}
@@ -1,5 +1,6 @@
 pub mod call_request;
 pub mod deploy_request;
 pub mod deposit_request;
+pub mod execute_request;
 pub mod transfer_request;
 pub mod withdraw_request;
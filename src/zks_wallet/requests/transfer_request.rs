@@ -1,4 +1,9 @@
-use ethers::types::{Address, Eip1559TransactionRequest, U256};
+use crate::{eip712::PaymasterParams, zks_utils::is_eth};
+use ethers::{
+    abi::{encode, Token},
+    types::{Address, Bytes, Eip1559TransactionRequest, U256},
+    utils::id,
+};
 use std::fmt::Debug;
 
 #[derive(Clone, Debug)]
@@ -6,6 +11,9 @@ pub struct TransferRequest {
     pub amount: U256,
     pub to: Address,
     pub from: Address,
+    /// ERC-20 token to transfer, ETH when `None`.
+    pub token: Option<Address>,
+    pub paymaster_params: Option<PaymasterParams>,
 }
 
 impl TransferRequest {
@@ -14,6 +22,8 @@ impl TransferRequest {
             amount,
             to: Default::default(),
             from: Default::default(),
+            token: None,
+            paymaster_params: None,
         }
     }
 
@@ -31,13 +41,43 @@ impl TransferRequest {
         self.amount = amount;
         self
     }
+
+    pub fn token(mut self, token: Address) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// The destination, value and calldata of the transfer: a plain value transfer for ETH, or a
+    /// call to the token's `transfer(address,uint256)`.
+    pub(crate) fn call(&self) -> (Address, U256, Bytes) {
+        match self.token.filter(|token| !is_eth(*token)) {
+            Some(token) => {
+                let data = [
+                    &id("transfer(address,uint256)")[..],
+                    &encode(&[Token::Address(self.to), Token::Uint(self.amount)]),
+                ]
+                .concat();
+                (token, U256::zero(), data.into())
+            }
+            None => (self.to, self.amount, Bytes::default()),
+        }
+    }
+
+    /// Lets a paymaster pay the fee of the transfer. `ZKSWallet::transfer` then sends it as an
+    /// EIP-712 transaction, the only type that carries paymaster params.
+    pub fn paymaster_params(mut self, paymaster_params: PaymasterParams) -> Self {
+        self.paymaster_params = Some(paymaster_params);
+        self
+    }
 }
 
 impl From<TransferRequest> for Eip1559TransactionRequest {
     fn from(request: TransferRequest) -> Eip1559TransactionRequest {
+        let (to, value, data) = request.call();
         Eip1559TransactionRequest::new()
-            .to(request.to)
-            .value(request.amount)
+            .to(to)
+            .value(value)
+            .data(data)
             .from(request.from)
     }
 }
@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use crate::eip712::PaymasterParams;
 use ethers::types::{Address, U256};
 
 #[derive(Clone, Debug)]
@@ -7,6 +8,11 @@ pub struct WithdrawRequest {
     pub amount: U256,
     pub to: Address,
     pub from: Address,
+    /// L2 address of the ERC-20 token to withdraw, ETH when `None`.
+    pub token: Option<Address>,
+    /// L2 bridge of the token, the default ERC-20 bridge when `None`.
+    pub bridge: Option<Address>,
+    pub paymaster_params: Option<PaymasterParams>,
 }
 
 impl WithdrawRequest {
@@ -15,6 +21,9 @@ impl WithdrawRequest {
             amount,
             to: Default::default(),
             from: Default::default(),
+            token: None,
+            bridge: None,
+            paymaster_params: None,
         }
     }
 
@@ -27,4 +36,21 @@ impl WithdrawRequest {
         self.from = from;
         self
     }
+
+    pub fn token(mut self, token: Address) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn bridge(mut self, bridge: Address) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
+    /// Lets a paymaster pay the L2 fee of the withdrawal. Finalizing it on L1 is still paid by
+    /// the L1 signer.
+    pub fn paymaster_params(mut self, paymaster_params: PaymasterParams) -> Self {
+        self.paymaster_params = Some(paymaster_params);
+        self
+    }
 }
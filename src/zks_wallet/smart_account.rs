@@ -0,0 +1,195 @@
+use super::{CallRequest, DeployRequest, SmartAccountError, TransferRequest};
+use crate::{
+    eip712::{Eip712Transaction, Eip712TransactionRequest, Eip712TransactionSigner},
+    zks_provider::ZKSProvider,
+    zks_utils::EIP712_TX_TYPE,
+};
+use async_trait::async_trait;
+use ethers::{
+    providers::Middleware,
+    types::{Address, Bytes, TransactionReceipt, H256},
+};
+use std::{fmt::Debug, sync::Arc};
+
+/// Produces the custom signature of the transactions sent by a smart account, i.e. the data
+/// checked by its `validateTransaction`.
+#[async_trait]
+pub trait AccountSignatureProvider: Debug + Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+
+    async fn custom_signature(&self, transaction: &Eip712Transaction)
+        -> Result<Bytes, Self::Error>;
+}
+
+/// Accounts owned by a single key, e.g. the default account or session keys, use the ECDSA
+/// signature of the transaction's EIP-712 digest.
+#[async_trait]
+impl<S> AccountSignatureProvider for S
+where
+    S: Eip712TransactionSigner,
+{
+    type Error = S::Error;
+
+    async fn custom_signature(
+        &self,
+        transaction: &Eip712Transaction,
+    ) -> Result<Bytes, Self::Error> {
+        Ok(self
+            .sign_transaction_eip712(transaction)
+            .await?
+            .to_vec()
+            .into())
+    }
+}
+
+/// Signs for multisig accounts by concatenating the signatures of all the owners, in order.
+#[derive(Clone, Debug)]
+pub struct MultisigSignatureProvider<S> {
+    pub owners: Vec<S>,
+}
+
+impl<S> MultisigSignatureProvider<S> {
+    pub fn new(owners: Vec<S>) -> Self {
+        Self { owners }
+    }
+}
+
+#[async_trait]
+impl<S> AccountSignatureProvider for MultisigSignatureProvider<S>
+where
+    S: Eip712TransactionSigner,
+{
+    type Error = S::Error;
+
+    async fn custom_signature(
+        &self,
+        transaction: &Eip712Transaction,
+    ) -> Result<Bytes, Self::Error> {
+        let mut signatures = Vec::new();
+        for owner in &self.owners {
+            signatures.extend(owner.sign_transaction_eip712(transaction).await?.to_vec());
+        }
+        Ok(signatures.into())
+    }
+}
+
+/// A smart contract account deployed with `createAccount` or `create2Account`. Its
+/// transactions are authorized by the custom signature of its [`AccountSignatureProvider`].
+#[derive(Clone, Debug)]
+pub struct SmartAccount<M, P> {
+    pub address: Address,
+    pub era_provider: Arc<M>,
+    pub signature_provider: P,
+}
+
+impl<M, P> SmartAccount<M, P>
+where
+    M: ZKSProvider + Middleware,
+    P: AccountSignatureProvider,
+{
+    pub fn new(address: Address, era_provider: M, signature_provider: P) -> Self {
+        Self {
+            address,
+            era_provider: Arc::new(era_provider),
+            signature_provider,
+        }
+    }
+
+    /// Fills, signs and sends a transaction from the account, waiting for its receipt.
+    pub async fn send_transaction_eip712<T>(
+        &self,
+        transaction: T,
+    ) -> Result<TransactionReceipt, SmartAccountError<M, P>>
+    where
+        T: TryInto<Eip712TransactionRequest>,
+        T::Error: Debug,
+    {
+        let era_provider = &self.era_provider;
+        let mut request: Eip712TransactionRequest = transaction.try_into().map_err(|e| {
+            SmartAccountError::CustomError(format!("Invalid transaction request: {e:?}"))
+        })?;
+
+        let gas_price = era_provider
+            .get_gas_price()
+            .await
+            .map_err(SmartAccountError::MiddlewareError)?;
+        request = request
+            .from(self.address)
+            .chain_id(
+                era_provider
+                    .get_chainid()
+                    .await
+                    .map_err(SmartAccountError::MiddlewareError)?,
+            )
+            .nonce(
+                era_provider
+                    .get_transaction_count(self.address, None)
+                    .await
+                    .map_err(SmartAccountError::MiddlewareError)?,
+            )
+            .gas_price(gas_price)
+            .max_fee_per_gas(gas_price);
+
+        let fee = era_provider.estimate_fee(request.clone()).await?;
+        request = request
+            .max_priority_fee_per_gas(fee.max_priority_fee_per_gas)
+            .max_fee_per_gas(fee.max_fee_per_gas)
+            .gas_limit(fee.gas_limit);
+
+        let signable_data: Eip712Transaction = request.clone().try_into()?;
+        let custom_signature = self
+            .signature_provider
+            .custom_signature(&signable_data)
+            .await
+            .map_err(SmartAccountError::SignatureProviderError)?;
+        let custom_data = request
+            .custom_data
+            .clone()
+            .custom_signature(custom_signature);
+        request = request.custom_data(custom_data);
+
+        let encoded_rlp = &*request.rlp_unsigned()?;
+        era_provider
+            .send_raw_transaction([&[EIP712_TX_TYPE], encoded_rlp].concat().into())
+            .await
+            .map_err(SmartAccountError::MiddlewareError)?
+            .await
+            .map_err(SmartAccountError::ProviderError)?
+            .ok_or(SmartAccountError::CustomError(
+                "No transaction receipt".to_owned(),
+            ))
+    }
+
+    pub async fn transfer(
+        &self,
+        request: &TransferRequest,
+    ) -> Result<H256, SmartAccountError<M, P>> {
+        let request = request.clone().from(self.address);
+        Ok(self
+            .send_transaction_eip712(request)
+            .await?
+            .transaction_hash)
+    }
+
+    /// Calls a non-view function of a contract from the account.
+    pub async fn send(&self, request: &CallRequest) -> Result<H256, SmartAccountError<M, P>> {
+        let request: Eip712TransactionRequest = request.clone().try_into()?;
+        Ok(self
+            .send_transaction_eip712(request)
+            .await?
+            .transaction_hash)
+    }
+
+    pub async fn deploy(
+        &self,
+        request: &DeployRequest,
+    ) -> Result<Address, SmartAccountError<M, P>> {
+        let request: Eip712TransactionRequest = request.clone().try_into()?;
+        self.send_transaction_eip712(request)
+            .await?
+            .contract_address
+            .ok_or(SmartAccountError::CustomError(
+                "No contract address".to_owned(),
+            ))
+    }
+}
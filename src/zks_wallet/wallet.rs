@@ -1,37 +1,44 @@
 use super::ZKSWalletError;
 use super::{
-    requests::transfer_request::TransferRequest, DeployRequest, DepositRequest, WithdrawRequest,
+    requests::transfer_request::TransferRequest, AccountAbstractionVersion,
+    AccountSignatureProvider, DeployRequest, DepositHandle, DepositQuote, DepositRequest,
+    ExecuteRequest, SmartAccount, TokenRateOracle, WithdrawRequest, ZKRequestError,
 };
 use crate::zks_utils::{
-    DEFAULT_ERC20_DEPOSIT_GAS_LIMIT, DEPOSIT_GAS_PER_PUBDATA_LIMIT, ERA_MAINNET_CHAIN_ID,
+    apply_l1_to_l2_alias, is_eth, undo_l1_to_l2_alias, BOOTLOADER_ADDRESS,
+    DEFAULT_ERC20_DEPOSIT_GAS_LIMIT, DEFAULT_ERC20_DEPOSIT_L2_GAS_LIMIT, ERA_MAINNET_CHAIN_ID,
 };
 use crate::{
     abi,
-    contracts::main_contract::{MainContract, MainContractInstance},
+    contracts::{
+        l1_bridge_contract::L1Bridge,
+        main_contract::{decode_new_priority_request, MainContract},
+    },
     eip712::Eip712Transaction,
-    eip712::{hash_bytecode, Eip712Meta, Eip712TransactionRequest},
+    eip712::{
+        hash_bytecode, Eip712Meta, Eip712TransactionRequest, Eip712TransactionSigner,
+        PaymasterParams,
+    },
     types::TransactionReceipt,
-    zks_provider::ZKSProvider,
+    zks_provider::{
+        types::{Fee, ZksTransactionReceipt},
+        ZKSProvider,
+    },
     zks_utils::{self, CONTRACT_DEPLOYER_ADDR, EIP712_TX_TYPE, ETHER_L1_ADDRESS, ETH_CHAIN_ID},
 };
 use ethers::{
-    abi::{decode, Abi, ParamType, Tokenizable},
-    prelude::{
-        encode_function_data,
-        k256::{
-            ecdsa::{RecoveryId, Signature as RecoverableSignature},
-            schnorr::signature::hazmat::PrehashSigner,
-        },
-        MiddlewareBuilder, SignerMiddleware,
-    },
+    abi::{decode, encode, Abi, ParamType, Token, Tokenizable},
+    prelude::{encode_function_data, MiddlewareBuilder, SignerMiddleware},
     providers::Middleware,
-    signers::{Signer, Wallet},
+    signers::Signer,
     types::{
         transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest, Log,
-        Signature, H160, H256, U256,
+        Signature, H160, H256, U256, U64,
     },
+    utils::{id, parse_units},
 };
 use lazy_static::lazy_static;
+use serde::Serialize;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::{fs::File, io::BufReader, path::PathBuf, str::FromStr, sync::Arc};
@@ -57,42 +64,94 @@ lazy_static! {
     };
 }
 
+/// Where a withdrawal stands on L1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// The batch of the withdrawal has not been executed on L1 yet.
+    NotExecuted,
+    /// The withdrawal can be finalized with `finalize_withdraw`.
+    ReadyToFinalize,
+    Finalized,
+}
+
+/// The arguments of `finalizeEthWithdrawal` and `finalizeWithdrawal`, along with the L1 contract
+/// to call: the main contract for ETH, the L1 bridge for ERC-20 tokens.
+struct WithdrawalProof {
+    l1_contract: Address,
+    is_eth_withdrawal: bool,
+    l1_batch_number: U256,
+    l2_message_index: U256,
+    l2_tx_number_in_block: U64,
+    message: Bytes,
+    merkle_proof: Vec<H256>,
+}
+
 #[derive(Clone, Debug)]
-pub struct ZKSWallet<M, D>
+pub struct ZKSWallet<M, L1, L2 = L1>
 where
     M: Middleware + Clone,
-    D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Clone,
+    L1: Signer + Clone,
+    L2: Eip712TransactionSigner + Clone,
 {
     /// Eth provider
-    pub eth_provider: Option<Arc<SignerMiddleware<M, Wallet<D>>>>,
-    pub era_provider: Option<Arc<SignerMiddleware<M, Wallet<D>>>>,
-    pub l2_wallet: Wallet<D>,
-    pub l1_wallet: Wallet<D>,
+    pub eth_provider: Option<Arc<SignerMiddleware<M, L1>>>,
+    pub era_provider: Option<Arc<SignerMiddleware<M, L2>>>,
+    pub l2_wallet: L2,
+    pub l1_wallet: L1,
+    /// Converts fees for approval-based paymasters, see [`Self::approval_based_paymaster_params`].
+    pub token_rate_oracle: Option<Arc<dyn TokenRateOracle>>,
 }
 
-impl<M, D> ZKSWallet<M, D>
+impl<M, S> ZKSWallet<M, S>
 where
     M: Middleware + 'static + Clone,
-    D: PrehashSigner<(RecoverableSignature, RecoveryId)> + Sync + Send + Clone,
+    S: Eip712TransactionSigner + Clone,
 {
+    /// Uses the same signer on both layers, `l1_wallet` defaults to `l2_wallet` on the L1 chain.
     pub fn new(
-        l2_wallet: Wallet<D>,
-        l1_wallet: Option<Wallet<D>>,
+        l2_wallet: S,
+        l1_wallet: Option<S>,
         era_provider: Option<M>,
         eth_provider: Option<M>,
-    ) -> Result<Self, ZKSWalletError<M, D>> {
+    ) -> Result<Self, ZKSWalletError<M, S>> {
         let l1_wallet = match l1_wallet {
             Some(wallet) => wallet,
             None => l2_wallet.clone().with_chain_id(ETH_CHAIN_ID),
         };
+        Self::from_signers(l2_wallet, l1_wallet, era_provider, eth_provider)
+    }
+}
+
+impl<M, L1, L2> ZKSWallet<M, L1, L2>
+where
+    M: Middleware + 'static + Clone,
+    L1: Signer + Clone,
+    L2: Eip712TransactionSigner + Clone,
+{
+    /// Pairs signers of different kinds, e.g. a remote L2 signer with a local L1 key.
+    pub fn from_signers(
+        l2_wallet: L2,
+        l1_wallet: L1,
+        era_provider: Option<M>,
+        eth_provider: Option<M>,
+    ) -> Result<Self, ZKSWalletError<M, L1, L2>> {
         Ok(Self {
             l2_wallet: l2_wallet.clone(),
             l1_wallet: l1_wallet.clone(),
             era_provider: era_provider.map(|p| p.with_signer(l2_wallet).into()),
             eth_provider: eth_provider.map(|p| p.with_signer(l1_wallet).into()),
+            token_rate_oracle: None,
         })
     }
 
+    pub fn with_token_rate_oracle<O>(mut self, token_rate_oracle: O) -> Self
+    where
+        O: TokenRateOracle + 'static,
+    {
+        self.token_rate_oracle = Some(Arc::new(token_rate_oracle));
+        self
+    }
+
     pub fn connect_eth_provider(mut self, eth_provider: M) -> Self {
         self.eth_provider = Some(eth_provider.with_signer(self.l1_wallet.clone()).into());
         self
@@ -103,12 +162,12 @@ where
         self
     }
 
-    pub fn connect_eth_signer(mut self, eth_signer: SignerMiddleware<M, Wallet<D>>) -> Self {
+    pub fn connect_eth_signer(mut self, eth_signer: SignerMiddleware<M, L1>) -> Self {
         self.eth_provider = Some(eth_signer.into());
         self
     }
 
-    pub fn connect_era_signer(mut self, era_signer: SignerMiddleware<M, Wallet<D>>) -> Self {
+    pub fn connect_era_signer(mut self, era_signer: SignerMiddleware<M, L2>) -> Self {
         self.era_provider = Some(era_signer.into());
         self
     }
@@ -139,7 +198,7 @@ where
 
     pub fn get_eth_provider(
         &self,
-    ) -> Result<Arc<SignerMiddleware<M, Wallet<D>>>, ZKSWalletError<M, D>> {
+    ) -> Result<Arc<SignerMiddleware<M, L1>>, ZKSWalletError<M, L1, L2>> {
         match &self.eth_provider {
             Some(eth_provider) => Ok(Arc::clone(eth_provider)),
             None => Err(ZKSWalletError::NoL1ProviderError()),
@@ -148,14 +207,14 @@ where
 
     pub fn get_era_provider(
         &self,
-    ) -> Result<Arc<SignerMiddleware<M, Wallet<D>>>, ZKSWalletError<M, D>> {
+    ) -> Result<Arc<SignerMiddleware<M, L2>>, ZKSWalletError<M, L1, L2>> {
         match &self.era_provider {
             Some(era_provider) => Ok(Arc::clone(era_provider)),
             None => Err(ZKSWalletError::NoL2ProviderError()),
         }
     }
 
-    pub async fn eth_balance(&self) -> Result<U256, ZKSWalletError<M, D>>
+    pub async fn eth_balance(&self) -> Result<U256, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
@@ -166,13 +225,16 @@ where
         }
     }
 
-    pub async fn era_balance(&self) -> Result<U256, ZKSWalletError<M, D>>
+    pub async fn era_balance(&self) -> Result<U256, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
         match &self.era_provider {
             // TODO: Should we have a balance_on_block method?
-            Some(era_provider) => Ok(era_provider.get_balance(self.l2_address(), None).await?),
+            Some(era_provider) => Ok(era_provider
+                .get_balance(self.l2_address(), None)
+                .await
+                .map_err(ZKSWalletError::L2MiddlewareError)?),
             None => Err(ZKSWalletError::CustomError("no era provider".to_owned())),
         }
     }
@@ -180,12 +242,15 @@ where
     pub async fn transfer(
         &self,
         request: &TransferRequest,
-        // TODO: Support multiple-token transfers.
-        _token: Option<Address>,
-    ) -> Result<H256, ZKSWalletError<M, D>>
+    ) -> Result<H256, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
+        // Paymasters can only be used from EIP-712 transactions.
+        if request.paymaster_params.is_some() {
+            return self.transfer_eip712(request).await;
+        }
+
         let era_provider = self.get_era_provider()?;
 
         let mut transfer_request: Eip1559TransactionRequest = request.clone().into();
@@ -199,7 +264,8 @@ where
         // TODO: add block as an override.
         let transaction_receipt = era_provider
             .send_transaction(transaction, None)
-            .await?
+            .await
+            .map_err(ZKSWalletError::L2MiddlewareError)?
             .await?
             .ok_or(ZKSWalletError::CustomError(
                 "No transaction receipt".to_owned(),
@@ -211,135 +277,257 @@ where
     pub async fn transfer_eip712(
         &self,
         request: &TransferRequest,
-        // TODO: Support multiple-token transfers.
-        _token: Option<Address>,
-    ) -> Result<H256, ZKSWalletError<M, D>>
+    ) -> Result<H256, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
-        let era_provider = self.get_era_provider()?;
+        let eip712_request: Eip712TransactionRequest = request.clone().into();
+        let transaction_receipt = self.send_transaction_eip712(eip712_request).await?;
 
-        let transaction_receipt = era_provider
-            .send_transaction_eip712(&self.l2_wallet, request.clone())
+        Ok(transaction_receipt.transaction_hash)
+    }
+
+    /// Deposits to L2, the returned handle can wait for the deposit to be executed on L2.
+    pub async fn deposit(
+        &self,
+        request: &DepositRequest,
+    ) -> Result<DepositHandle<SignerMiddleware<M, L2>>, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let eth_provider = self.get_eth_provider()?;
+        let mut quote = self.quote_deposit(request).await?;
+
+        if let Some(approval) = quote.approval {
+            eth_provider
+                .send_transaction(approval, None)
+                .await?
+                .await?
+                .ok_or(ZKSWalletError::CustomError(
+                    "No transaction receipt for erc20 approval".to_owned(),
+                ))?;
+            // The bridge can only be estimated once it is allowed to pull the tokens.
+            if request.gas_limit.is_none() {
+                let gas_limit = eth_provider
+                    .estimate_gas(&quote.transaction.clone().into(), None)
+                    .await?;
+                quote.transaction.gas = Some(gas_limit);
+            }
+        }
+        let receipt = eth_provider
+            .send_transaction(quote.transaction, None)
             .await?
             .await?
             .ok_or(ZKSWalletError::CustomError(
-                "No transaction receipt".to_owned(),
+                "no transaction receipt".to_owned(),
             ))?;
+        let main_contract_address = self.get_era_provider()?.get_main_contract().await?;
+        let l2_tx_hash = l2_hash_from_receipt(&receipt, main_contract_address)?;
 
-        Ok(transaction_receipt.transaction_hash)
+        Ok(DepositHandle::new(
+            receipt.transaction_hash,
+            l2_tx_hash,
+            self.get_era_provider()?,
+        ))
     }
 
-    pub async fn deposit(&self, request: &DepositRequest) -> Result<H256, ZKSWalletError<M, D>>
+    /// Computes what [`Self::deposit`] would send, and what it would cost, without sending it.
+    pub async fn quote_deposit(
+        &self,
+        request: &DepositRequest,
+    ) -> Result<DepositQuote, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
         let to = request.to.unwrap_or(self.l2_address());
-        let call_data = Bytes::default();
-        let l2_gas_limit: U256 = request.l2_gas_limit;
-        let l2_value = request.amount;
-        let gas_per_pubdata_byte: U256 = request.gas_per_pubdata_byte;
-        let gas_price = request
-            .gas_price
-            .unwrap_or(self.get_eth_provider()?.get_gas_price().await?);
-        let gas_limit: U256 = request.gas_limit;
-        let operator_tip: U256 = request.operator_tip;
-        let base_cost = self
-            .get_base_cost(gas_limit, gas_per_pubdata_byte, gas_price)
+        let fees = self
+            .l1_fees(
+                request.max_fee_per_gas.or(request.gas_price),
+                request.max_priority_fee_per_gas,
+            )
             .await?;
-        let l1_value = base_cost + operator_tip + request.amount;
-        // let factory_deps = [];
-        let refund_recipient = self.l1_address();
-        // FIXME check base cost
 
-        let receipt = if request.token == ETHER_L1_ADDRESS {
-            let main_contract_address = self.get_era_provider()?.get_main_contract().await?;
-            let main_contract =
-                MainContractInstance::new(main_contract_address, self.get_eth_provider()?);
-
-            main_contract
-                .request_l2_transaction(
-                    to,
-                    l2_value,
-                    call_data,
-                    l2_gas_limit,
-                    gas_per_pubdata_byte,
-                    Default::default(),
-                    refund_recipient,
-                    gas_price,
-                    gas_limit,
-                    l1_value,
-                )
-                .await?
+        if request.token == ETHER_L1_ADDRESS {
+            self.quote_eth_deposit(request, to, fees).await
         } else {
-            self.deposit_erc20_token(
-                request.token,
-                request.amount().to_owned(),
-                to,
-                operator_tip,
-                request.bridge_address,
-                None,
-                Some(gas_price),
-            )
+            self.quote_erc20_deposit(request, to, fees).await
+        }
+    }
+
+    /// Calls an L2 contract from L1 through the priority queue, the L2 gas limit is estimated
+    /// when not set and the base cost is added to the L1 value.
+    pub async fn request_execute(
+        &self,
+        request: &ExecuteRequest,
+    ) -> Result<DepositHandle<SignerMiddleware<M, L2>>, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let quote = self.quote_request_execute(request).await?;
+        let receipt = self
+            .get_eth_provider()?
+            .send_transaction(quote.transaction, None)
+            .await?
             .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "no transaction receipt".to_owned(),
+            ))?;
+        let main_contract_address = self.get_era_provider()?.get_main_contract().await?;
+        let l2_tx_hash = l2_hash_from_receipt(&receipt, main_contract_address)?;
+
+        Ok(DepositHandle::new(
+            receipt.transaction_hash,
+            l2_tx_hash,
+            self.get_era_provider()?,
+        ))
+    }
+
+    /// Computes what [`Self::request_execute`] would send, and what it would cost, without
+    /// sending it.
+    pub async fn quote_request_execute(
+        &self,
+        request: &ExecuteRequest,
+    ) -> Result<DepositQuote, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let era_provider = self.get_era_provider()?;
+        let eth_provider = self.get_eth_provider()?;
+        let main_contract_address = era_provider.get_main_contract().await?;
+        let l2_gas_limit = match request.l2_gas_limit {
+            Some(l2_gas_limit) => l2_gas_limit,
+            None => {
+                era_provider
+                    .estimate_gas_l1_to_l2(L1ToL2Transaction {
+                        from: self.l1_address(),
+                        to: request.contract_address,
+                        data: request.calldata.clone(),
+                        value: request.l2_value,
+                        eip712_meta: Eip712Meta::new()
+                            .gas_per_pubdata(request.gas_per_pubdata_byte)
+                            .factory_deps(
+                                request
+                                    .factory_deps
+                                    .iter()
+                                    .map(|dep| dep.to_vec())
+                                    .collect::<Vec<_>>(),
+                            ),
+                    })
+                    .await?
+            }
         };
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self
+            .l1_fees(request.max_fee_per_gas, request.max_priority_fee_per_gas)
+            .await?;
+        let base_cost = self
+            .get_base_cost(l2_gas_limit, request.gas_per_pubdata_byte, max_fee_per_gas)
+            .await?;
+        let l1_value = base_cost + request.operator_tip + request.l2_value;
 
-        Ok(receipt.transaction_hash)
+        let call = MainContract::new(main_contract_address, Arc::clone(&eth_provider))
+            .request_l2_transaction(
+                request.contract_address,
+                request.l2_value,
+                request.calldata.clone(),
+                l2_gas_limit,
+                request.gas_per_pubdata_byte,
+                request.factory_deps.clone(),
+                request.refund_recipient.unwrap_or(self.l1_address()),
+            )
+            .from(self.l1_address())
+            .value(l1_value);
+        let data = call.calldata().ok_or(ZKSWalletError::CustomError(
+            "failed to encode requestL2Transaction".to_owned(),
+        ))?;
+        let gas_limit = match request.gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => call.estimate_gas().await?,
+        };
+
+        Ok(DepositQuote {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            l2_gas_limit,
+            base_cost,
+            operator_tip: request.operator_tip,
+            approval: None,
+            transaction: Eip1559TransactionRequest::new()
+                .from(self.l1_address())
+                .to(main_contract_address)
+                .gas(gas_limit)
+                .value(l1_value)
+                .data(data)
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                .chain_id(eth_provider.get_chainid().await?.as_u64()),
+        })
     }
 
-    async fn deposit_erc20_token(
+    /// An ETH deposit is a `requestL2Transaction` to the recipient with the amount as L2 value
+    /// and no calldata.
+    async fn quote_eth_deposit(
         &self,
-        l1_token_address: Address,
-        amount: U256,
+        request: &DepositRequest,
         to: Address,
-        operator_tip: U256,
-        bridge_address: Option<Address>,
-        max_fee_per_gas: Option<U256>,
-        gas_price: Option<U256>,
-    ) -> Result<TransactionReceipt, ZKSWalletError<M, D>>
+        (max_fee_per_gas, max_priority_fee_per_gas): (U256, U256),
+    ) -> Result<DepositQuote, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let execute_request = ExecuteRequest::new(to, Bytes::default())
+            .l2_value(request.amount)
+            .l2_gas_limit(request.l2_gas_limit)
+            .gas_per_pubdata_byte(request.gas_per_pubdata_byte)
+            .operator_tip(request.operator_tip)
+            .max_fee_per_gas(Some(max_fee_per_gas))
+            .max_priority_fee_per_gas(Some(max_priority_fee_per_gas))
+            .gas_limit(request.gas_limit);
+
+        self.quote_request_execute(&execute_request).await
+    }
+
+    async fn quote_erc20_deposit(
+        &self,
+        request: &DepositRequest,
+        to: Address,
+        (max_fee_per_gas, max_priority_fee_per_gas): (U256, U256),
+    ) -> Result<DepositQuote, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
         let eth_provider = self.get_eth_provider()?;
         let era_provider = self.get_era_provider()?;
-
-        let gas_limit: U256 = {
-            let address_str = format!("{l1_token_address:?}");
-            let is_mainnet =
-                self.get_era_provider()?.get_chainid().await? == ERA_MAINNET_CHAIN_ID.into();
-            if is_mainnet {
-                (*ERC20_DEPOSIT_GAS_LIMITS)
-                    .get(&address_str)
-                    .unwrap_or(&DEFAULT_ERC20_DEPOSIT_GAS_LIMIT)
-                    .to_owned()
-            } else {
-                DEFAULT_ERC20_DEPOSIT_GAS_LIMIT
+        let l1_token_address = request.token;
+        let amount = request.amount;
+        let bridge_contracts = era_provider.get_bridge_contracts().await?;
+        let bridge_address = request
+            .bridge_address
+            .unwrap_or(bridge_contracts.l1_erc20_default_bridge);
+
+        let l2_gas_limit = match request.l2_gas_limit {
+            Some(l2_gas_limit) => l2_gas_limit,
+            // Only the L2 counterpart of the default bridge is known.
+            None if bridge_address == bridge_contracts.l1_erc20_default_bridge => {
+                self.estimate_erc20_deposit_l2_gas(
+                    bridge_address,
+                    bridge_contracts.l2_erc20_default_bridge,
+                    l1_token_address,
+                    amount,
+                    to,
+                    request.gas_per_pubdata_byte,
+                )
+                .await?
             }
-        }
-        .into();
-
-        // If the user has already provided max_fee_per_gas or gas_price, we will use
-        // it to calculate the base cost for the transaction
-        let gas_price = if let Some(max_fee_per_gas) = max_fee_per_gas {
-            max_fee_per_gas
-        } else if let Some(gas_price) = gas_price {
-            gas_price
-        } else {
-            era_provider.get_gas_price().await?
+            None => DEFAULT_ERC20_DEPOSIT_L2_GAS_LIMIT.into(),
         };
 
-        let l2_gas_limit = U256::from(3_000_000_u32);
-
         let base_cost: U256 = self
-            .get_base_cost(
-                l2_gas_limit,
-                DEPOSIT_GAS_PER_PUBDATA_LIMIT.into(),
-                gas_price,
-            )
+            .get_base_cost(l2_gas_limit, request.gas_per_pubdata_byte, max_fee_per_gas)
             .await?;
 
         // ERC20 token, `msg.value` is used only for the fee.
-        let value = base_cost + operator_tip;
+        let value = base_cost + request.operator_tip;
 
         let data: Bytes = {
             let bridge_contract = abi::l1_bridge_contract();
@@ -354,7 +542,7 @@ where
                 l1_token_address,
                 amount,
                 l2_gas_limit,
-                U256::from(DEPOSIT_GAS_PER_PUBDATA_LIMIT),
+                request.gas_per_pubdata_byte,
             );
 
             #[allow(clippy::expect_used)]
@@ -366,67 +554,213 @@ where
 
         let chain_id = eth_provider.get_chainid().await?.as_u64();
 
-        let bridge_address: Address = match bridge_address {
-            Some(address) => address,
-            None => {
-                let bridge_contracts = era_provider.get_bridge_contracts().await?;
-                bridge_contracts.l1_erc20_default_bridge
-            }
-        };
-
-        let deposit_transaction = Eip1559TransactionRequest {
+        let mut deposit_transaction = Eip1559TransactionRequest {
             from: Some(self.get_eth_provider()?.address()),
             to: Some(bridge_address.into()),
-            gas: Some(gas_limit),
+            gas: request.gas_limit,
             value: Some(value),
             data: Some(data),
             nonce: None,
             access_list: Default::default(),
-            max_priority_fee_per_gas: None, // FIXME
-            max_fee_per_gas: None,          // FIXME
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+            max_fee_per_gas: Some(max_fee_per_gas),
             chain_id: Some(chain_id.into()),
         };
 
-        let _approve_tx_receipt = self
-            .approve_erc20(bridge_address, amount, l1_token_address)
-            .await?;
-        let pending_transaction = eth_provider
-            .send_transaction(deposit_transaction, None)
-            .await?;
-
-        pending_transaction
+        let approval = self
+            .erc20_approval(bridge_address, amount, l1_token_address)
             .await?
-            .ok_or(ZKSWalletError::CustomError(
-                "no transaction receipt".to_owned(),
-            ))
+            .map(|approval| {
+                approval
+                    .max_fee_per_gas(max_fee_per_gas)
+                    .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                    .chain_id(chain_id)
+            });
+        let gas_limit = match deposit_transaction.gas {
+            Some(gas_limit) => gas_limit,
+            // The bridge can only be estimated once it is allowed to pull the tokens.
+            None if approval.is_none() => {
+                eth_provider
+                    .estimate_gas(&deposit_transaction.clone().into(), None)
+                    .await?
+            }
+            None => self.erc20_deposit_gas_limit(l1_token_address).await?,
+        };
+        deposit_transaction.gas = Some(gas_limit);
+
+        Ok(DepositQuote {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            l2_gas_limit,
+            base_cost,
+            operator_tip: request.operator_tip,
+            approval,
+            transaction: deposit_transaction,
+        })
+    }
+
+    /// Fills the missing EIP-1559 fees of an L1 transaction from the L1 fee history, returning the
+    /// max fee and the priority fee.
+    async fn l1_fees(
+        &self,
+        max_fee_per_gas: Option<U256>,
+        max_priority_fee_per_gas: Option<U256>,
+    ) -> Result<(U256, U256), ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
+            (max_fee_per_gas, max_priority_fee_per_gas)
+        {
+            return Ok((
+                max_fee_per_gas,
+                max_priority_fee_per_gas.min(max_fee_per_gas),
+            ));
+        }
+
+        let (estimated_max_fee_per_gas, estimated_max_priority_fee_per_gas) =
+            self.get_eth_provider()?.estimate_eip1559_fees(None).await?;
+        Ok(match (max_fee_per_gas, max_priority_fee_per_gas) {
+            (Some(max_fee_per_gas), _) => (
+                max_fee_per_gas,
+                estimated_max_priority_fee_per_gas.min(max_fee_per_gas),
+            ),
+            (None, Some(max_priority_fee_per_gas)) => (
+                estimated_max_fee_per_gas.max(max_priority_fee_per_gas),
+                max_priority_fee_per_gas,
+            ),
+            (None, None) => (
+                estimated_max_fee_per_gas,
+                estimated_max_priority_fee_per_gas,
+            ),
+        })
+    }
+
+    /// The L1 gas limit of an ERC-20 deposit when it can't be estimated, from the mainnet table.
+    async fn erc20_deposit_gas_limit(
+        &self,
+        l1_token_address: Address,
+    ) -> Result<U256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let address_str = format!("{l1_token_address:?}");
+        let is_mainnet = self
+            .get_era_provider()?
+            .get_chainid()
+            .await
+            .map_err(ZKSWalletError::L2MiddlewareError)?
+            == ERA_MAINNET_CHAIN_ID.into();
+        let gas_limit = if is_mainnet {
+            (*ERC20_DEPOSIT_GAS_LIMITS)
+                .get(&address_str)
+                .unwrap_or(&DEFAULT_ERC20_DEPOSIT_GAS_LIMIT)
+                .to_owned()
+        } else {
+            DEFAULT_ERC20_DEPOSIT_GAS_LIMIT
+        };
+        Ok(gas_limit.into())
     }
 
-    async fn approve_erc20(
+    /// Estimates the L2 gas of an ERC-20 deposit, that is the `finalizeDeposit` call of the
+    /// L1 bridge to the L2 bridge with the token metadata.
+    async fn estimate_erc20_deposit_l2_gas(
+        &self,
+        l1_bridge: Address,
+        l2_bridge: Address,
+        l1_token_address: Address,
+        amount: U256,
+        to: Address,
+        gas_per_pubdata_byte: U256,
+    ) -> Result<U256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let eth_provider = self.get_eth_provider()?;
+        let mut metadata = Vec::new();
+        for function in ["name()", "symbol()", "decimals()"] {
+            let call: TypedTransaction = Eip1559TransactionRequest::new()
+                .to(l1_token_address)
+                .data(id(function).to_vec())
+                .into();
+            metadata.push(Token::Bytes(eth_provider.call(&call, None).await?.to_vec()));
+        }
+        let data = abi::l2_bridge_contract()
+            .and_then(|bridge| {
+                bridge.function("finalizeDeposit")?.encode_input(&[
+                    Token::Address(self.l1_address()),
+                    Token::Address(to),
+                    Token::Address(l1_token_address),
+                    Token::Uint(amount),
+                    Token::Bytes(encode(&metadata)),
+                ])
+            })
+            .map_err(ZKRequestError::from)?;
+
+        let l2_gas_limit = self
+            .get_era_provider()?
+            .estimate_gas_l1_to_l2(L1ToL2Transaction {
+                from: apply_l1_to_l2_alias(l1_bridge),
+                to: l2_bridge,
+                data: data.into(),
+                value: U256::zero(),
+                eip712_meta: Eip712Meta::new().gas_per_pubdata(gas_per_pubdata_byte),
+            })
+            .await?;
+        Ok(l2_gas_limit)
+    }
+
+    /// The approval letting `bridge` pull `amount` of `token`, `None` when the allowance is
+    /// already enough.
+    async fn erc20_approval(
         &self,
         bridge: Address,
         amount: U256,
         token: Address,
-    ) -> Result<TransactionReceipt, ZKSWalletError<M, D>>
+    ) -> Result<Option<Eip1559TransactionRequest>, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
         let provider = self.get_eth_provider()?;
-        let function_signature =
-            "function approve(address spender,uint256 amount) public virtual returns (bool)";
-        let parameters = [format!("{bridge:?}"), format!("{amount:?}")];
-        let response = provider
-            .send(
-                &self.l1_wallet,
-                token,
-                function_signature,
-                Some(parameters.into()),
-                None,
+        let allowance_call: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(token)
+            .data(
+                [
+                    &id("allowance(address,address)")[..],
+                    &encode(&[Token::Address(self.l1_address()), Token::Address(bridge)]),
+                ]
+                .concat(),
             )
-            .await?;
+            .into();
+        let allowance = decode(
+            &[ParamType::Uint(256)],
+            &provider.call(&allowance_call, None).await?,
+        )
+        .map_err(|e| ZKSWalletError::CustomError(format!("failed to decode allowance: {e}")))?
+        .pop()
+        .and_then(|allowance| allowance.into_uint())
+        .ok_or(ZKSWalletError::CustomError(
+            "Token allowance not found".to_owned(),
+        ))?;
+        if allowance >= amount {
+            return Ok(None);
+        }
 
-        response.await?.ok_or(ZKSWalletError::CustomError(
-            "No transaction receipt for erc20 approval".to_owned(),
-        ))
+        let approval = Eip1559TransactionRequest::new()
+            .from(self.l1_address())
+            .to(token)
+            .data(
+                [
+                    &id("approve(address,uint256)")[..],
+                    &encode(&[Token::Address(bridge), Token::Uint(amount)]),
+                ]
+                .concat(),
+            );
+        let gas_limit = provider
+            .estimate_gas(&approval.clone().into(), None)
+            .await?;
+        Ok(Some(approval.gas(gas_limit)))
     }
 
     async fn get_base_cost(
@@ -434,7 +768,7 @@ where
         gas_limit: U256,
         gas_per_pubdata_byte: U256,
         gas_price: U256,
-    ) -> Result<U256, ZKSWalletError<M, D>>
+    ) -> Result<U256, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
@@ -454,7 +788,7 @@ where
         contract_dependencies: Option<Vec<Vec<u8>>>,
         // TODO: accept constructor parameters.
         _constructor_parameters: Option<T>,
-    ) -> Result<H160, ZKSWalletError<M, D>>
+    ) -> Result<H160, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
         T: Tokenizable,
@@ -482,10 +816,21 @@ where
             .nonce(
                 era_provider
                     .get_transaction_count(self.l2_address(), None)
-                    .await?,
+                    .await
+                    .map_err(ZKSWalletError::L2MiddlewareError)?,
+            )
+            .gas_price(
+                era_provider
+                    .get_gas_price()
+                    .await
+                    .map_err(ZKSWalletError::L2MiddlewareError)?,
+            )
+            .max_fee_per_gas(
+                era_provider
+                    .get_gas_price()
+                    .await
+                    .map_err(ZKSWalletError::L2MiddlewareError)?,
             )
-            .gas_price(era_provider.get_gas_price().await?)
-            .max_fee_per_gas(era_provider.get_gas_price().await?)
             .data({
                 let contract_deployer = Abi::load(BufReader::new(
                     File::open(contract_deployer_path).map_err(|e| {
@@ -516,14 +861,19 @@ where
             .gas_limit(fee.gas_limit);
 
         let signable_data: Eip712Transaction = deploy_request.clone().try_into()?;
-        let signature: Signature = self.l2_wallet.sign_typed_data(&signable_data).await?;
+        let signature: Signature = self
+            .l2_wallet
+            .sign_transaction_eip712(&signable_data)
+            .await
+            .map_err(ZKSWalletError::SignerError)?;
         deploy_request =
             deploy_request.custom_data(custom_data.custom_signature(signature.to_vec()));
 
         let encoded_rlp = &*deploy_request.rlp_signed(signature)?;
         let pending_transaction = era_provider
             .send_raw_transaction([&[EIP712_TX_TYPE], encoded_rlp].concat().into())
-            .await?;
+            .await
+            .map_err(ZKSWalletError::L2MiddlewareError)?;
 
         // TODO: Should we wait here for the transaction to be confirmed on-chain?
 
@@ -543,21 +893,13 @@ where
         Ok(contract_address)
     }
 
-    pub async fn deploy(&self, request: &DeployRequest) -> Result<H160, ZKSWalletError<M, D>>
+    pub async fn deploy(&self, request: &DeployRequest) -> Result<H160, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
-        let era_provider = self.get_era_provider()?;
-
         let eip712_request: Eip712TransactionRequest = request.clone().try_into()?;
 
-        let transaction_receipt = era_provider
-            .send_transaction_eip712(&self.l2_wallet, eip712_request)
-            .await?
-            .await?
-            .ok_or(ZKSWalletError::CustomError(
-                "No transaction receipt".to_owned(),
-            ))?;
+        let transaction_receipt = self.send_transaction_eip712(eip712_request).await?;
 
         transaction_receipt
             .contract_address
@@ -566,32 +908,266 @@ where
             ))
     }
 
-    pub async fn withdraw(&self, request: &WithdrawRequest) -> Result<H256, ZKSWalletError<M, D>>
+    /// Deploys an account contract through `createAccount`, or `create2Account` when the request
+    /// has a salt, returning the [`SmartAccount`] that signs its transactions with
+    /// `signature_provider`.
+    pub async fn deploy_account<P>(
+        &self,
+        request: &DeployRequest,
+        signature_provider: P,
+    ) -> Result<SmartAccount<M, P>, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+        P: AccountSignatureProvider,
+    {
+        let mut request = request.clone();
+        if request.account_abstraction_version == AccountAbstractionVersion::None {
+            request = request.account_abstraction_version(AccountAbstractionVersion::Version1);
+        }
+        let address = self.deploy(&request).await?;
+
+        Ok(SmartAccount::new(
+            address,
+            self.get_era_provider()?.inner().clone(),
+            signature_provider,
+        ))
+    }
+
+    pub async fn withdraw(
+        &self,
+        request: &WithdrawRequest,
+    ) -> Result<H256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let mut request = request.clone();
+        if request.bridge.is_none() && request.token.is_some_and(|token| !is_eth(token)) {
+            let bridge_contracts = self.get_era_provider()?.get_bridge_contracts().await?;
+            request = request.bridge(bridge_contracts.l2_erc20_default_bridge);
+        }
+        let eip712_request: Eip712TransactionRequest = request.try_into()?;
+        let transaction_receipt = self.send_transaction_eip712(eip712_request).await?;
+
+        Ok(transaction_receipt.transaction_hash)
+    }
+
+    async fn send_transaction_eip712(
+        &self,
+        request: Eip712TransactionRequest,
+    ) -> Result<TransactionReceipt, ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
         let era_provider = self.get_era_provider()?;
+
         let transaction_receipt = era_provider
-            .send_transaction_eip712(&self.l2_wallet, request.clone())
+            .send_transaction_eip712(&self.l2_wallet, request)
             .await?
             .await?
             .ok_or(ZKSWalletError::CustomError(
                 "No transaction receipt".to_owned(),
             ))?;
 
+        Ok(transaction_receipt)
+    }
+
+    /// Params for the `approvalBased` flow of `paymaster` whose minimal allowance covers the fee
+    /// of `request` (`max_fee_per_gas * gas_limit`), converted into `token` with
+    /// [`Self::fee_in_token`].
+    ///
+    /// The fee is estimated with a minimal allowance of 1, so a paymaster that takes the whole
+    /// allowance must accept being paid that much during estimation. Requests are estimated
+    /// again when sent and the fee may have risen by then, use
+    /// [`Self::send_with_approval_based_paymaster`] to sign the fee the allowance covers.
+    pub async fn approval_based_paymaster_params(
+        &self,
+        paymaster: Address,
+        token: Address,
+        inner_input: Bytes,
+        request: &Eip712TransactionRequest,
+    ) -> Result<PaymasterParams, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let (paymaster_params, _) = self
+            .quote_approval_based_paymaster(paymaster, token, inner_input, request)
+            .await?;
+        Ok(paymaster_params)
+    }
+
+    /// Sends `request` with the `approvalBased` flow of `paymaster`, sized as in
+    /// [`Self::approval_based_paymaster_params`]. The gas limit and fees the allowance was sized
+    /// for are the ones signed, they are not estimated again.
+    pub async fn send_with_approval_based_paymaster(
+        &self,
+        paymaster: Address,
+        token: Address,
+        inner_input: Bytes,
+        request: Eip712TransactionRequest,
+    ) -> Result<H256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let era_provider = self.get_era_provider()?;
+
+        let (paymaster_params, fee) = self
+            .quote_approval_based_paymaster(paymaster, token, inner_input, &request)
+            .await?;
+        let custom_data = request
+            .custom_data
+            .clone()
+            .paymaster_params(paymaster_params);
+        let request = request
+            .from(self.l2_address())
+            .chain_id(self.l2_chain_id())
+            .nonce(
+                era_provider
+                    .get_transaction_count(self.l2_address(), None)
+                    .await
+                    .map_err(ZKSWalletError::L2MiddlewareError)?,
+            )
+            .max_priority_fee_per_gas(fee.max_priority_fee_per_gas)
+            .max_fee_per_gas(fee.max_fee_per_gas)
+            .gas_limit(fee.gas_limit)
+            .custom_data(custom_data.clone());
+
+        let signable_data: Eip712Transaction = request.clone().try_into()?;
+        let signature: Signature = self
+            .l2_wallet
+            .sign_transaction_eip712(&signable_data)
+            .await
+            .map_err(ZKSWalletError::SignerError)?;
+        let request = request.custom_data(custom_data.custom_signature(signature.to_vec()));
+
+        let encoded_rlp = &*request.rlp_signed(signature)?;
+        let transaction_receipt = era_provider
+            .send_raw_transaction([&[EIP712_TX_TYPE], encoded_rlp].concat().into())
+            .await
+            .map_err(ZKSWalletError::L2MiddlewareError)?
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "No transaction receipt".to_owned(),
+            ))?;
+
         Ok(transaction_receipt.transaction_hash)
     }
 
-    pub async fn finalize_withdraw(&self, tx_hash: H256) -> Result<H256, ZKSWalletError<M, D>>
+    /// Estimates the fee of `request` with the `approvalBased` flow of `paymaster` and returns
+    /// the params whose allowance covers it, along with the estimate.
+    async fn quote_approval_based_paymaster(
+        &self,
+        paymaster: Address,
+        token: Address,
+        inner_input: Bytes,
+        request: &Eip712TransactionRequest,
+    ) -> Result<(PaymasterParams, Fee), ZKSWalletError<M, L1, L2>>
     where
         M: ZKSProvider,
     {
         let era_provider = self.get_era_provider()?;
-        let eth_provider = self.get_eth_provider()?;
 
-        let withdrawal_receipt = era_provider.get_transaction_receipt(tx_hash).await?.ok_or(
-            ZKSWalletError::CustomError("Error getting transaction receipt of withdraw".to_owned()),
-        )?;
+        let estimation_params =
+            PaymasterParams::approval_based(paymaster, token, 1_u64.into(), inner_input.clone())
+                .map_err(ZKRequestError::from)?;
+        let custom_data = request
+            .custom_data
+            .clone()
+            .paymaster_params(estimation_params);
+        let request = request
+            .clone()
+            .from(self.l2_address())
+            .chain_id(self.l2_chain_id())
+            .custom_data(custom_data);
+        let fee = era_provider.estimate_fee(request).await?;
+        let max_fee =
+            fee.max_fee_per_gas
+                .checked_mul(fee.gas_limit)
+                .ok_or(ZKSWalletError::CustomError(
+                    "estimated fee overflows".to_owned(),
+                ))?;
+        let allowance = self.fee_in_token(token, max_fee).await?;
+        let paymaster_params =
+            PaymasterParams::approval_based(paymaster, token, allowance, inner_input)
+                .map_err(ZKRequestError::from)?;
+
+        Ok((paymaster_params, fee))
+    }
+
+    /// Converts `fee` wei into `token` with the wallet's oracle or, without one, with the
+    /// prices of ETH and the token reported by `zks_getTokenPrice`. Rounds up.
+    pub async fn fee_in_token(
+        &self,
+        token: Address,
+        fee: U256,
+    ) -> Result<U256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        if let Some(oracle) = &self.token_rate_oracle {
+            return Ok(oracle.fee_in_token(token, fee).await?);
+        }
+
+        let era_provider = self.get_era_provider()?;
+        let parse_price = |price: String| -> Result<U256, ZKSWalletError<M, L1, L2>> {
+            parse_units(&price, "ether").map(U256::from).map_err(|e| {
+                ZKSWalletError::CustomError(format!("invalid token price {price}: {e}"))
+            })
+        };
+        let eth_price = parse_price(era_provider.get_token_price(Address::zero()).await?)?;
+        let token_price = parse_price(era_provider.get_token_price(token).await?)?;
+        if token_price.is_zero() {
+            return Err(ZKSWalletError::CustomError(format!(
+                "no price for token {token:?}"
+            )));
+        }
+
+        let decimals_call: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(token)
+            .data(id("decimals()").to_vec())
+            .into();
+        let decimals = decode(
+            &[ParamType::Uint(8)],
+            &era_provider
+                .call(&decimals_call, None)
+                .await
+                .map_err(ZKSWalletError::L2MiddlewareError)?,
+        )
+        .map_err(|e| ZKSWalletError::CustomError(format!("failed to decode decimals: {e}")))?
+        .pop()
+        .and_then(|decimals| decimals.into_uint())
+        .ok_or(ZKSWalletError::CustomError(
+            "Token decimals not found".to_owned(),
+        ))?;
+
+        // 10^78 does not fit in 256 bits.
+        if decimals > 77_u64.into() {
+            return Err(ZKSWalletError::UnsupportedTokenDecimals { token, decimals });
+        }
+        let overflow = || ZKSWalletError::FeeConversionOverflow { token, fee };
+        let value = fee
+            .checked_mul(eth_price)
+            .and_then(|value| value.checked_mul(U256::exp10(decimals.as_usize())))
+            .ok_or_else(overflow)?;
+        let denominator = token_price
+            .checked_mul(U256::exp10(18))
+            .ok_or_else(overflow)?;
+        Ok(value
+            .checked_add(denominator - U256::one())
+            .ok_or_else(overflow)?
+            / denominator)
+    }
+
+    /// Gathers what's needed to finalize the withdrawal `tx_hash` on L1, failing when its batch
+    /// has no proof yet.
+    async fn withdrawal_proof(
+        &self,
+        tx_hash: H256,
+        withdrawal_receipt: ZksTransactionReceipt,
+    ) -> Result<WithdrawalProof, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let era_provider = self.get_era_provider()?;
 
         let messenger_contract_address = Address::from_str(zks_utils::CONTRACTS_L1_MESSENGER_ADDR)
             .map_err(|error| {
@@ -599,6 +1175,7 @@ where
             })?;
 
         let logs: Vec<Log> = withdrawal_receipt
+            .receipt
             .logs
             .into_iter()
             .filter(|log| {
@@ -608,30 +1185,14 @@ where
             .collect();
 
         // Get all the parameters needed to call the finalizeWithdrawal function on the main contract contract.
-        let (_, l2_to_l1_log_index) = serde_json::from_value::<Vec<Value>>(
-            withdrawal_receipt
-                .other
-                .get("l2ToL1Logs")
-                .ok_or(ZKSWalletError::CustomError(
-                    "Field not present in receipt".to_owned(),
-                ))?
-                .clone(),
-        )
-        .map_err(|err| {
-            ZKSWalletError::CustomError(format!("Error getting logs in receipt: {err:?}"))
-        })?
-        .iter()
-        .zip(0_u64..)
-        .find(|(log, _)| {
-            if let Some(sender) = log.get("sender") {
-                sender == zks_utils::CONTRACTS_L1_MESSENGER_ADDR
-            } else {
-                false
-            }
-        })
-        .ok_or(ZKSWalletError::CustomError(
-            "Error getting log index parameter".to_owned(),
-        ))?;
+        let (_, l2_to_l1_log_index) = withdrawal_receipt
+            .l2_to_l1_logs
+            .iter()
+            .zip(0_u64..)
+            .find(|(log, _)| log.sender == messenger_contract_address)
+            .ok_or(ZKSWalletError::CustomError(
+                "Error getting log index parameter".to_owned(),
+            ))?;
 
         let filtered_log = logs
             .get(0)
@@ -639,27 +1200,34 @@ where
                 "Error getting log in receipt".to_owned(),
             ))?
             .clone();
+        // The L2 contract that sent the message: L2EthToken for ETH, a bridge for ERC-20 tokens.
+        let message_sender = filtered_log
+            .topics
+            .get(1)
+            .map(|topic| Address::from(*topic))
+            .ok_or(ZKSWalletError::CustomError(
+                "Message sender not found in log".to_owned(),
+            ))?;
         let proof = era_provider
             .get_l2_to_l1_log_proof(tx_hash, Some(l2_to_l1_log_index))
             .await?
             .ok_or(ZKSWalletError::CustomError(
                 "Error getting proof parameter".to_owned(),
             ))?;
-        let main_contract = era_provider.get_main_contract().await?;
-        let merkle_proof: Vec<H256> = proof.merkle_proof;
-        let l1_batch_number = era_provider.get_l1_batch_number().await?;
-        let l2_message_index = U256::from(proof.id);
+        // The batch of the withdrawal itself, not the latest one.
+        let l1_batch_number =
+            withdrawal_receipt
+                .l1_batch_number
+                .ok_or(ZKSWalletError::CustomError(
+                    "Withdrawal is not included in an L1 batch yet".to_owned(),
+                ))?;
 
-        let l2_tx_number_in_block: String = serde_json::from_value::<String>(
+        let l2_tx_number_in_block =
             withdrawal_receipt
-                .other
-                .get("l1BatchTxIndex")
+                .l1_batch_tx_index
                 .ok_or(ZKSWalletError::CustomError(
                     "Field not present in receipt".to_owned(),
-                ))?
-                .clone(),
-        )
-        .map_err(|err| ZKSWalletError::CustomError(format!("Failed to deserialize field {err}")))?;
+                ))?;
 
         let message: Bytes = decode(&[ParamType::Bytes], &filtered_log.data)
             .map_err(|e| ZKSWalletError::CustomError(format!("failed to decode log data: {e}")))?
@@ -674,21 +1242,64 @@ where
             ))?
             .into();
 
+        let (l1_contract, is_eth_withdrawal) = if is_eth(message_sender) {
+            (era_provider.get_main_contract().await?, true)
+        } else {
+            (self.l1_bridge(message_sender).await?, false)
+        };
+
+        Ok(WithdrawalProof {
+            l1_contract,
+            is_eth_withdrawal,
+            l1_batch_number: l1_batch_number.as_u64().into(),
+            l2_message_index: U256::from(proof.id),
+            l2_tx_number_in_block,
+            message,
+            merkle_proof: proof.merkle_proof,
+        })
+    }
+
+    pub async fn finalize_withdraw(&self, tx_hash: H256) -> Result<H256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let eth_provider = self.get_eth_provider()?;
+        let withdrawal_receipt = self
+            .get_era_provider()?
+            .get_zks_transaction_receipt(tx_hash)
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "Error getting transaction receipt of withdraw".to_owned(),
+            ))?;
+        let WithdrawalProof {
+            l1_contract,
+            is_eth_withdrawal,
+            l1_batch_number,
+            l2_message_index,
+            l2_tx_number_in_block,
+            message,
+            merkle_proof,
+        } = self.withdrawal_proof(tx_hash, withdrawal_receipt).await?;
+
         let parameters = [
             format!("{l1_batch_number:?}"),
             format!("{l2_message_index:?}"),
-            l2_tx_number_in_block,
+            format!("{l2_tx_number_in_block:?}"),
             hex::encode(&message),
             format!("{merkle_proof:?}")
                 .replace('"', "")
                 .replace(' ', ""),
         ];
 
-        let function_signature = "function finalizeEthWithdrawal(uint256 _l2BlockNumber,uint256 _l2MessageIndex,uint16 _l2TxNumberInBlock,bytes calldata _message,bytes32[] calldata _merkleProof) external";
+        let function_signature = if is_eth_withdrawal {
+            "function finalizeEthWithdrawal(uint256 _l2BlockNumber,uint256 _l2MessageIndex,uint16 _l2TxNumberInBlock,bytes calldata _message,bytes32[] calldata _merkleProof) external"
+        } else {
+            "function finalizeWithdrawal(uint256 _l2BlockNumber,uint256 _l2MessageIndex,uint16 _l2TxNumberInBlock,bytes calldata _message,bytes32[] calldata _merkleProof) external"
+        };
         let transaction_receipt = eth_provider
             .send(
                 &self.l1_wallet,
-                main_contract,
+                l1_contract,
                 function_signature,
                 Some(parameters.into()),
                 None,
@@ -701,4 +1312,244 @@ where
 
         Ok(transaction_receipt.transaction_hash)
     }
+
+    /// Tells whether the withdrawal `tx_hash` still waits for its batch to be executed on L1,
+    /// can be finalized, or was already finalized.
+    pub async fn withdrawal_status(
+        &self,
+        tx_hash: H256,
+    ) -> Result<WithdrawalStatus, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let era_provider = self.get_era_provider()?;
+        let eth_provider = self.get_eth_provider()?;
+        let withdrawal_receipt = era_provider
+            .get_zks_transaction_receipt(tx_hash)
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "Error getting transaction receipt of withdraw".to_owned(),
+            ))?;
+        let Some(l1_batch_number) = withdrawal_receipt.l1_batch_number else {
+            return Ok(WithdrawalStatus::NotExecuted);
+        };
+
+        let main_contract = MainContract::new(
+            era_provider.get_main_contract().await?,
+            Arc::clone(&eth_provider),
+        );
+        let total_batches_executed = main_contract.get_total_blocks_executed().call().await?;
+        if U256::from(l1_batch_number.as_u64()) > total_batches_executed {
+            return Ok(WithdrawalStatus::NotExecuted);
+        }
+
+        let proof = self.withdrawal_proof(tx_hash, withdrawal_receipt).await?;
+        let is_finalized = if proof.is_eth_withdrawal {
+            main_contract
+                .is_eth_withdrawal_finalized(proof.l1_batch_number, proof.l2_message_index)
+                .call()
+                .await?
+        } else {
+            L1Bridge::new(proof.l1_contract, eth_provider)
+                .is_withdrawal_finalized(proof.l1_batch_number, proof.l2_message_index)
+                .call()
+                .await?
+        };
+
+        Ok(if is_finalized {
+            WithdrawalStatus::Finalized
+        } else {
+            WithdrawalStatus::ReadyToFinalize
+        })
+    }
+
+    pub async fn is_withdrawal_finalized(
+        &self,
+        tx_hash: H256,
+    ) -> Result<bool, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        Ok(self.withdrawal_status(tx_hash).await? == WithdrawalStatus::Finalized)
+    }
+
+    /// Returns the hash of the L2 transaction requested by the L1 transaction `l1_tx_hash`, e.g.
+    /// a deposit, from its `NewPriorityRequest` event.
+    pub async fn l2_hash_of_l1_transaction(
+        &self,
+        l1_tx_hash: H256,
+    ) -> Result<H256, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let main_contract = self.get_era_provider()?.get_main_contract().await?;
+        let l1_receipt = self
+            .get_eth_provider()?
+            .get_transaction_receipt(l1_tx_hash)
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "No transaction receipt".to_owned(),
+            ))?;
+
+        l2_hash_from_receipt(&l1_receipt, main_contract)
+    }
+
+    /// Gets back the tokens of a bridge deposit that failed on L2, given the hash of the L1
+    /// deposit transaction.
+    pub async fn claim_failed_deposit(
+        &self,
+        l1_deposit_tx_hash: H256,
+    ) -> Result<TransactionReceipt, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let era_provider = self.get_era_provider()?;
+        let l2_tx_hash = self.l2_hash_of_l1_transaction(l1_deposit_tx_hash).await?;
+        let l2_receipt = era_provider
+            .get_zks_transaction_receipt(l2_tx_hash)
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "Deposit not processed on L2 yet".to_owned(),
+            ))?;
+        if l2_receipt.receipt.status == Some(1_u64.into()) {
+            return Err(ZKSWalletError::DepositSucceeded(l2_tx_hash));
+        }
+
+        // Deposits are executed on L2 by the (aliased) L1 bridge, calling `finalizeDeposit` on
+        // its L2 counterpart.
+        let l2_transaction = era_provider
+            .get_transaction(l2_tx_hash)
+            .await
+            .map_err(ZKSWalletError::L2MiddlewareError)?
+            .ok_or(ZKSWalletError::CustomError(
+                "Deposit transaction not found on L2".to_owned(),
+            ))?;
+        let l1_bridge = undo_l1_to_l2_alias(l2_receipt.receipt.from);
+        let finalize_deposit = abi::l2_bridge_contract()
+            .and_then(|bridge| bridge.function("finalizeDeposit").cloned())
+            .map_err(ZKRequestError::from)?;
+        let mut deposit = finalize_deposit
+            .decode_input(l2_transaction.input.get(4..).unwrap_or_default())
+            .map_err(ZKRequestError::from)?
+            .into_iter();
+        let (Some(Token::Address(deposit_sender)), _, Some(Token::Address(l1_token))) =
+            (deposit.next(), deposit.next(), deposit.next())
+        else {
+            return Err(ZKSWalletError::CustomError(
+                "Deposit transaction is not a bridge deposit".to_owned(),
+            ));
+        };
+
+        // The bootloader logs the outcome of L1->L2 transactions, keyed by their hash.
+        let (_, l2_to_l1_log_index) = l2_receipt
+            .l2_to_l1_logs
+            .iter()
+            .zip(0_u64..)
+            .find(|(log, _)| log.sender == BOOTLOADER_ADDRESS && log.key == l2_tx_hash)
+            .ok_or(ZKSWalletError::CustomError(
+                "Deposit log not found in receipt".to_owned(),
+            ))?;
+        let proof = era_provider
+            .get_l2_to_l1_log_proof(l2_tx_hash, Some(l2_to_l1_log_index))
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "Error getting proof parameter".to_owned(),
+            ))?;
+        let l1_batch_number = l2_receipt
+            .l1_batch_number
+            .ok_or(ZKSWalletError::CustomError(
+                "Deposit is not included in an L1 batch yet".to_owned(),
+            ))?;
+        let l2_tx_number_in_block =
+            l2_receipt
+                .l1_batch_tx_index
+                .ok_or(ZKSWalletError::CustomError(
+                    "Field not present in receipt".to_owned(),
+                ))?;
+
+        L1Bridge::new(l1_bridge, self.get_eth_provider()?)
+            .claim_failed_deposit(
+                deposit_sender,
+                l1_token,
+                l2_tx_hash.0,
+                l1_batch_number.as_u64().into(),
+                proof.id.into(),
+                u16::try_from(l2_tx_number_in_block).map_err(|e| {
+                    ZKSWalletError::CustomError(format!("invalid transaction index: {e}"))
+                })?,
+                proof.merkle_proof.into_iter().map(|hash| hash.0).collect(),
+            )
+            .send()
+            .await?
+            .await?
+            .ok_or(ZKSWalletError::CustomError(
+                "No transaction receipt".to_owned(),
+            ))
+    }
+
+    /// Returns the L1 counterpart of the L2 bridge `l2_bridge`.
+    async fn l1_bridge(&self, l2_bridge: Address) -> Result<Address, ZKSWalletError<M, L1, L2>>
+    where
+        M: ZKSProvider,
+    {
+        let era_provider = self.get_era_provider()?;
+        let bridge_contracts = era_provider.get_bridge_contracts().await?;
+        if l2_bridge == bridge_contracts.l2_erc20_default_bridge {
+            return Ok(bridge_contracts.l1_erc20_default_bridge);
+        }
+
+        let l1_bridge = abi::l2_bridge_contract()
+            .and_then(|bridge| bridge.function("l1Bridge").cloned())
+            .map_err(ZKRequestError::from)?;
+        let l1_bridge_call: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(l2_bridge)
+            .data(l1_bridge.encode_input(&[]).map_err(ZKRequestError::from)?)
+            .into();
+        l1_bridge
+            .decode_output(
+                &era_provider
+                    .call(&l1_bridge_call, None)
+                    .await
+                    .map_err(ZKSWalletError::L2MiddlewareError)?,
+            )
+            .map_err(ZKRequestError::from)?
+            .pop()
+            .and_then(|l1_bridge| l1_bridge.into_address())
+            .ok_or(ZKSWalletError::CustomError(
+                "L1 bridge not found".to_owned(),
+            ))
+    }
+}
+
+/// Finds the hash of the L2 transaction requested in an L1 receipt, from the
+/// `NewPriorityRequest` event of the main contract.
+fn l2_hash_from_receipt<M, L1, L2>(
+    receipt: &TransactionReceipt,
+    main_contract: Address,
+) -> Result<H256, ZKSWalletError<M, L1, L2>>
+where
+    M: Middleware,
+    L1: Signer,
+    L2: Signer,
+{
+    receipt
+        .logs
+        .iter()
+        .filter(|log| log.address == main_contract)
+        .find_map(|log| decode_new_priority_request(log).ok())
+        .map(|request| H256(request.tx_hash))
+        .ok_or(ZKSWalletError::CustomError(
+            "NewPriorityRequest event not found in receipt".to_owned(),
+        ))
+}
+
+/// The L1 to L2 transaction sent to `zks_estimateGasL1ToL2`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct L1ToL2Transaction {
+    from: Address,
+    to: Address,
+    data: Bytes,
+    value: U256,
+    eip712_meta: Eip712Meta,
 }
@@ -0,0 +1,83 @@
+#![cfg(feature = "mock")]
+
+use async_trait::async_trait;
+use std::str::FromStr;
+use zksync_web3_rs::eip712::TypedDataSigner;
+use zksync_web3_rs::mock::MockZKSNode;
+use zksync_web3_rs::signers::{LocalWallet, Signer, WalletError};
+use zksync_web3_rs::types::transaction::{eip2718::TypedTransaction, eip712::Eip712};
+use zksync_web3_rs::types::{Address, Signature};
+use zksync_web3_rs::utils::parse_ether;
+use zksync_web3_rs::zks_utils::ERA_CHAIN_ID;
+use zksync_web3_rs::zks_wallet::TransferRequest;
+use zksync_web3_rs::ZKSWallet;
+
+const PRIVATE_KEY: &str = "0x28a574ab2de8a00364d5dd4b07c4f2f574ef7fcc2a86a197f65abaec836d1959";
+
+/// A signer defined outside of the crate, like ethers' `Ledger` or `AwsSigner`.
+#[derive(Clone, Debug)]
+struct ExternalSigner(LocalWallet);
+
+#[async_trait]
+impl Signer for ExternalSigner {
+    type Error = WalletError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        self.0.sign_message(message).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        self.0.sign_transaction(tx).await
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        self.0.sign_typed_data(payload).await
+    }
+
+    fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.0.chain_id()
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        Self(self.0.with_chain_id(chain_id))
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_with_external_signer() {
+    let era_node = MockZKSNode::new(ERA_CHAIN_ID);
+    let wallet = LocalWallet::from_str(PRIVATE_KEY)
+        .unwrap()
+        .with_chain_id(ERA_CHAIN_ID);
+    let signer = TypedDataSigner(ExternalSigner(wallet));
+    let receiver_address = Address::from_low_u64_be(0x1234);
+    era_node.set_balance(signer.address(), parse_ether("10").unwrap());
+
+    let zk_wallet = ZKSWallet::new(signer, None, Some(era_node.provider()), None).unwrap();
+    let request = TransferRequest::new(parse_ether("1").unwrap())
+        .to(receiver_address)
+        .from(zk_wallet.l2_address());
+    let eip712_tx_hash = zk_wallet.transfer_eip712(&request).await.unwrap();
+    let tx_hash = zk_wallet.transfer(&request).await.unwrap();
+    let sent_transactions = era_node.sent_transactions();
+
+    assert_eq!(sent_transactions.len(), 2);
+    assert_eq!(sent_transactions[0].hash, eip712_tx_hash);
+    assert_eq!(sent_transactions[0].from, zk_wallet.l2_address());
+    assert_eq!(sent_transactions[1].hash, tx_hash);
+    assert_eq!(sent_transactions[1].from, zk_wallet.l2_address());
+    assert_eq!(
+        era_node.balance(receiver_address),
+        parse_ether("2").unwrap()
+    );
+}